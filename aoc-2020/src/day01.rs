@@ -1,25 +1,48 @@
 use crate::iter::tails;
-use std::{collections::HashSet, num::ParseIntError};
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+use std::collections::HashSet;
 
-#[aoc_generator(day1)]
-fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    input.lines().map(str::parse).collect()
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = Option<u32>;
+    type Part2 = Option<u32>;
+    const PART1_VARIANTS = [part1_hashed];
+    const PART2_VARIANTS = [part2_hashed];
+    const GENERATOR = Generator { generate, size: 200 };
 }
 
-#[aoc_generator(day1, part1, HashSet)]
-fn parse_input_hashed(input: &str) -> Result<HashSet<u32>, ParseIntError> {
-    input.lines().map(str::parse).collect()
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .lines()
+        .map(|line| aoc_core::parse::number(input, line))
+        .collect()
 }
 
-#[aoc(day1, part1)]
-fn part1(data: &[u32]) -> Option<u32> {
+/// `size` expenses, of which exactly one pair and one triple add up to
+/// 2020: the rest are too big to be in either.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pair = rng.range(700..1000u32);
+    let (x, y) = (rng.range(100..300u32), rng.range(100..300u32));
+    let mut expenses = vec![pair, 2020 - pair, x, y, 2020 - x - y];
+    expenses.extend((expenses.len()..size).map(|_| rng.range(1950..2000)));
+    rng.shuffle(&mut expenses);
+
+    let mut expenses = expenses.into_iter();
+    aoc_core::generate::lines(rng, size, |_| expenses.next().unwrap().to_string())
+}
+
+pub fn part1(data: &[u32]) -> Option<u32> {
     tails(data)
         .flat_map(move |(&x, xs)| xs.iter().map(move |&y| (x, y)))
         .find_map(|(x, y)| if x + y == 2020 { Some(x * y) } else { None })
 }
 
-#[aoc(day1, part1, HashSet)]
-fn part1_hashed(set: &HashSet<u32>) -> Option<u32> {
+pub fn part1_hashed(data: &[u32]) -> Option<u32> {
+    let set = data.iter().cloned().collect::<HashSet<_>>();
+
     set.iter().find_map(|value| {
         2020u32.checked_sub(*value).and_then(|target| {
             if set.contains(&target) {
@@ -31,8 +54,7 @@ fn part1_hashed(set: &HashSet<u32>) -> Option<u32> {
     })
 }
 
-#[aoc(day1, part2)]
-fn part2(data: &[u32]) -> Option<u32> {
+pub fn part2(data: &[u32]) -> Option<u32> {
     tails(data)
         .flat_map(move |(&x, xs)| {
             tails(xs).flat_map(move |(&y, ys)| ys.iter().map(move |&z| (x, y, z)))
@@ -46,8 +68,7 @@ fn part2(data: &[u32]) -> Option<u32> {
         })
 }
 
-#[aoc(day1, part2, HashSet)]
-fn part2_hashed(data: &[u32]) -> Option<u32> {
+pub fn part2_hashed(data: &[u32]) -> Option<u32> {
     let set = data.iter().cloned().collect::<HashSet<_>>();
 
     tails(data)
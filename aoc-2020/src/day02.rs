@@ -1,15 +1,23 @@
 use crate::parsers::range;
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::{alphanumeric1, satisfy},
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::map,
     multi::separated_list1,
-    sequence::separated_pair,
-    Finish, IResult,
+    sequence::{separated_pair, terminated},
 };
 use std::ops::RangeInclusive;
 
+aoc_core::solution! {
+    type Input = Vec<(Rule, Vec<u8>)>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Rule {
     pub args: (usize, usize),
@@ -43,19 +51,31 @@ fn parse_entries(input: &str) -> IResult<&str, Vec<(Rule, Vec<u8>)>> {
     separated_list1(tag("\n"), parse_entry)(input)
 }
 
-#[aoc_generator(day2)]
-fn parse_input(input: &str) -> Result<Vec<(Rule, Vec<u8>)>, Error<String>> {
-    match all_consuming(parse_entries)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<(Rule, Vec<u8>)>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_entries, tag("\n")))
 }
 
-#[aoc(day2, part1)]
-fn part1(data: &[(Rule, Vec<u8>)]) -> usize {
+/// `size` passwords, each with a policy for a letter it often uses, and
+/// at least as long as the policy's positions reach.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let letter = char::from(rng.range(b'a'..b'z' + 1));
+        let low = rng.range(1..10);
+        let high = rng.range(low + 1..20);
+        let password: String = (0..rng.range(high..high + 5))
+            .map(|_| {
+                if rng.chance(0.4) {
+                    letter
+                } else {
+                    char::from(rng.range(b'a'..b'z' + 1))
+                }
+            })
+            .collect();
+        format!("{}-{} {}: {}", low, high, letter, password)
+    })
+}
+
+pub fn part1(data: &[(Rule, Vec<u8>)]) -> usize {
     data.iter()
         .filter(|(rule, password)| {
             let count = bytecount::count(password, rule.target);
@@ -64,8 +84,7 @@ fn part1(data: &[(Rule, Vec<u8>)]) -> usize {
         .count()
 }
 
-#[aoc(day2, part2)]
-fn part2(data: &[(Rule, Vec<u8>)]) -> usize {
+pub fn part2(data: &[(Rule, Vec<u8>)]) -> usize {
     data.iter()
         .filter(|(rule, password)| {
             let first = password[rule.args.0 - 1];
@@ -74,3 +93,38 @@ fn part2(data: &[(Rule, Vec<u8>)]) -> usize {
         })
         .count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn entries() -> impl Strategy<Value = Vec<(Rule, Vec<u8>)>> {
+        let rule = (1..20usize, 1..20usize, b'a'..=b'z').prop_map(|(low, high, target)| Rule {
+            args: (low, high),
+            target,
+        });
+        prop::collection::vec((rule, "[a-z0-9]{1,20}".prop_map(String::into_bytes)), 1..20)
+    }
+
+    fn render(entries: &[(Rule, Vec<u8>)]) -> String {
+        entries
+            .iter()
+            .map(|(rule, password)| {
+                format!(
+                    "{}-{} {}: {}\n",
+                    rule.args.0,
+                    rule.args.1,
+                    rule.target as char,
+                    String::from_utf8_lossy(password)
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(entries, |entries| render(entries), parse_input);
+    }
+}
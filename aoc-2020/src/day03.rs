@@ -1,68 +1,55 @@
-use crate::parsers::grid;
-use nom::{
-    combinator::{all_consuming, map},
-    error::Error,
-    Finish, IResult,
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::{tag, ParseError},
 };
+use nom::sequence::terminated;
+
+aoc_core::solution! {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 323 };
+}
 
 #[derive(Debug, PartialEq)]
-enum Tile {
+pub enum Tile {
     Tree,
     Empty,
 }
 
 #[derive(Debug)]
-struct Map {
-    tiles: Vec<Tile>,
-    width: usize,
-}
+pub struct Map(Grid<Tile>);
 
 impl Map {
     fn path(&self, (right, down): (usize, usize)) -> usize {
         (0..)
-            .map(|step| {
-                self.tiles
-                    .get(step * down * self.width + step * right % self.width)
-            })
+            .map(|step| self.0.get(step * right % self.0.width(), step * down))
             .take_while(|tile| tile.is_some())
             .filter(|tile| tile.unwrap() == &Tile::Tree)
             .count()
     }
 }
 
-fn parse_map(input: &str) -> IResult<&str, Map> {
-    map(grid("#."), |(grid, (width, _))| {
-        let tiles = grid
-            .into_iter()
-            .map(|b| match b {
-                b'#' => Tile::Tree,
-                b'.' => Tile::Empty,
-                _ => unreachable!(),
-            })
-            .collect();
-
-        Map { tiles, width }
-    })(input)
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let map = aoc_core::grid::parser(|c| match c {
+        '#' => Some(Tile::Tree),
+        '.' => Some(Tile::Empty),
+        _ => None,
+    });
+    aoc_core::parse::all(input, terminated(map, tag("\n"))).map(Map)
 }
 
-#[aoc_generator(day3)]
-fn parse_input(input: &str) -> Result<Map, Error<String>> {
-    match all_consuming(parse_map)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+/// A slope `size` rows long, 31 wide like the puzzle's.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::grid(rng, 31, size, |rng| if rng.chance(0.2) { '#' } else { '.' })
 }
 
-#[aoc(day3, part1)]
-fn part1(map: &Map) -> usize {
+pub fn part1(map: &Map) -> usize {
     map.path((3, 1))
 }
 
-#[aoc(day3, part2)]
-fn part2(map: &Map) -> usize {
+pub fn part2(map: &Map) -> usize {
     [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
         .iter()
         .map(|&slope| map.path(slope))
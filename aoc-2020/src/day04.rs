@@ -1,16 +1,22 @@
 use crate::parsers::number;
+use aoc_core::parse::{tag, Error, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while_m_n},
+    bytes::complete::take_while_m_n,
     character::complete::{alphanumeric1, one_of, satisfy},
     combinator::{all_consuming, map, map_res, recognize},
-    error::Error,
     multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair},
-    Finish, IResult,
+    sequence::{preceded, separated_pair, terminated},
+    Finish,
 };
 use std::{collections::HashMap, str::FromStr};
 
+aoc_core::solution! {
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 #[derive(Debug)]
 enum Measurement {
     Metric(u32),
@@ -31,27 +37,25 @@ impl FromStr for Measurement {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match all_consuming(parse_measurement)(s).finish() {
             Ok((_, measurement)) => Ok(measurement),
-            Err(Error { input, code }) => Err(Error {
+            Err(Error { input, expected }) => Err(Error {
                 input: input.to_string(),
-                code,
+                expected,
             }),
         }
     }
 }
 
 #[derive(Debug)]
-struct HexColor(String);
+struct HexColor;
 
 fn is_hex_digit(c: char) -> bool {
-    c.is_digit(16)
+    c.is_ascii_hexdigit()
 }
 
 fn parse_hex(input: &str) -> IResult<&str, HexColor> {
     preceded(
         tag("#"),
-        map(take_while_m_n(6, 6, is_hex_digit), |s: &str| {
-            HexColor(s.to_string())
-        }),
+        map(take_while_m_n(6, 6, is_hex_digit), |_| HexColor),
     )(input)
 }
 
@@ -61,9 +65,9 @@ impl FromStr for HexColor {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match all_consuming(parse_hex)(s).finish() {
             Ok((_, color)) => Ok(color),
-            Err(Error { input, code }) => Err(Error {
+            Err(Error { input, expected }) => Err(Error {
                 input: input.to_string(),
-                code,
+                expected,
             }),
         }
     }
@@ -98,23 +102,25 @@ impl FromStr for Color {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match all_consuming(parse_color)(s).finish() {
             Ok((_, color)) => Ok(color),
-            Err(Error { input, code }) => Err(Error {
+            Err(Error { input, expected }) => Err(Error {
                 input: input.to_string(),
-                code,
+                expected,
             }),
         }
     }
 }
 
 #[derive(Debug)]
-struct Pid(u32);
+struct Pid;
 
 fn is_digit(c: char) -> bool {
-    c.is_digit(10)
+    c.is_ascii_digit()
 }
 
 fn parse_pid(input: &str) -> IResult<&str, Pid> {
-    map_res(take_while_m_n(9, 9, is_digit), |s: &str| s.parse().map(Pid))(input)
+    map_res(take_while_m_n(9, 9, is_digit), |s: &str| {
+        s.parse::<u32>().map(|_| Pid)
+    })(input)
 }
 
 impl FromStr for Pid {
@@ -123,16 +129,16 @@ impl FromStr for Pid {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match all_consuming(parse_pid)(s).finish() {
             Ok((_, pid)) => Ok(pid),
-            Err(Error { input, code }) => Err(Error {
+            Err(Error { input, expected }) => Err(Error {
                 input: input.to_string(),
-                code,
+                expected,
             }),
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct Passport(HashMap<String, String>);
+pub struct Passport(HashMap<String, String>);
 
 impl Passport {
     fn birth_year(&self) -> Option<u32> {
@@ -175,14 +181,14 @@ impl Passport {
 
     fn is_valid_strict(&self) -> bool {
         self.birth_year()
-            .map_or(false, |year| (1920..=2002).contains(&year))
+            .is_some_and(|year| (1920..=2002).contains(&year))
             && self
                 .issue_year()
-                .map_or(false, |year| (2010..=2020).contains(&year))
+                .is_some_and(|year| (2010..=2020).contains(&year))
             && self
                 .expiration_year()
-                .map_or(false, |year| (2020..=2030).contains(&year))
-            && self.height().map_or(false, |value| match value {
+                .is_some_and(|year| (2020..=2030).contains(&year))
+            && self.height().is_some_and(|value| match value {
                 Measurement::Metric(height) => (150..=193).contains(&height),
                 Measurement::Imperial(height) => (59..=76).contains(&height),
             })
@@ -213,24 +219,15 @@ fn parse_passports(input: &str) -> IResult<&str, Vec<Passport>> {
     separated_list1(tag("\n\n"), parse_passport)(input)
 }
 
-#[aoc_generator(day4)]
-fn parse_input(input: &str) -> Result<Vec<Passport>, Error<String>> {
-    match all_consuming(parse_passports)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Passport>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_passports, tag("\n")))
 }
 
-#[aoc(day4, part1)]
-fn part1(data: &[Passport]) -> usize {
+pub fn part1(data: &[Passport]) -> usize {
     data.iter().filter(|passport| passport.is_valid()).count()
 }
 
-#[aoc(day4, part2)]
-fn part2(data: &[Passport]) -> usize {
+pub fn part2(data: &[Passport]) -> usize {
     data.iter()
         .filter(|passport| passport.is_valid_strict())
         .count()
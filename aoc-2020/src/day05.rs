@@ -1,13 +1,24 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
 use std::collections::BinaryHeap;
 
+aoc_core::solution! {
+    type Input = Vec<Seat>;
+    type Part1 = Option<u32>;
+    type Part2 = Option<u32>;
+    const GENERATOR = Generator { generate, size: 800 };
+}
+
 #[derive(Debug)]
-enum Op {
+pub enum Op {
     Top,
     Bottom,
 }
 
 #[derive(Debug)]
-struct Seat {
+pub struct Seat {
     row: Vec<Op>,
     column: Vec<Op>,
 }
@@ -31,38 +42,58 @@ fn bsp(ops: &[Op]) -> u32 {
     })
 }
 
-#[aoc_generator(day5)]
-fn parse_input(input: &str) -> Vec<Seat> {
+fn parse_ops(half: &str, top: char, bottom: char) -> Option<Vec<Op>> {
+    half.chars()
+        .map(|c| match c {
+            c if c == top => Some(Op::Top),
+            c if c == bottom => Some(Op::Bottom),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_seat(line: &str) -> Option<Seat> {
+    Some(Seat {
+        row: parse_ops(line.get(0..7)?, 'F', 'B')?,
+        column: parse_ops(line.get(7..)?, 'L', 'R').filter(|column| column.len() == 3)?,
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Seat>, ParseError> {
     input
         .lines()
-        .map(|line| Seat {
-            row: line[0..7]
-                .chars()
-                .map(|c| match c {
-                    'F' => Op::Top,
-                    'B' => Op::Bottom,
-                    _ => unreachable!(),
-                })
-                .collect(),
-            column: line[7..]
-                .chars()
-                .map(|c| match c {
-                    'L' => Op::Top,
-                    'R' => Op::Bottom,
-                    _ => unreachable!(),
-                })
-                .collect(),
-        })
+        .map(|line| parse_seat(line).ok_or_else(|| ParseError::new(input, line, "seat")))
         .collect()
 }
 
-#[aoc(day5, part1)]
-fn part1(data: &[Seat]) -> Option<u32> {
+/// `size` boarding passes for a run of seats, with one free in the
+/// middle.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let first = rng.range(8..1024 - size as u32 - 8);
+    let free = first + rng.range(1..size as u32);
+    let mut seats: Vec<u32> = (first..=first + size as u32)
+        .filter(|&id| id != free)
+        .collect();
+    rng.shuffle(&mut seats);
+
+    let mut seats = seats.into_iter();
+    aoc_core::generate::lines(rng, size, |_| {
+        let id = seats.next().unwrap();
+        let half = |bits: u32, width: u32, top, bottom| {
+            (0..width)
+                .rev()
+                .map(|bit| if bits >> bit & 1 == 0 { top } else { bottom })
+                .collect::<String>()
+        };
+        half(id >> 3, 7, 'F', 'B') + &half(id & 7, 3, 'L', 'R')
+    })
+}
+
+pub fn part1(data: &[Seat]) -> Option<u32> {
     data.iter().map(|seat| seat.seat_id()).max()
 }
 
-#[aoc(day5, part2)]
-fn part2(data: &[Seat]) -> Option<u32> {
+pub fn part2(data: &[Seat]) -> Option<u32> {
     let seats = data
         .iter()
         .map(|seat| seat.seat_id())
@@ -71,7 +102,7 @@ fn part2(data: &[Seat]) -> Option<u32> {
 
     seats.windows(2).find_map(|data| {
         if data[0] + 1 != data[1] {
-            Some(data[0] as u32 + 1)
+            Some(data[0] + 1)
         } else {
             None
         }
@@ -1,14 +1,20 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::alpha1,
-    combinator::{all_consuming, map},
-    error::Error,
-    multi::separated_list1,
-    Finish, IResult,
+    character::complete::alpha1, combinator::map, multi::separated_list1, sequence::terminated,
 };
 use std::collections::HashSet;
 
-struct AnswerSheet(Vec<HashSet<char>>);
+aoc_core::solution! {
+    type Input = Vec<AnswerSheet>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 500 };
+}
+
+pub struct AnswerSheet(Vec<HashSet<char>>);
 
 impl AnswerSheet {
     fn union(&self) -> HashSet<char> {
@@ -39,23 +45,38 @@ fn parse_answers(input: &str) -> IResult<&str, Vec<AnswerSheet>> {
     separated_list1(tag("\n\n"), parse_answer)(input)
 }
 
-#[aoc_generator(day6)]
-fn parse_input(input: &str) -> Result<Vec<AnswerSheet>, Error<String>> {
-    match all_consuming(parse_answers)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<AnswerSheet>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_answers, tag("\n")))
+}
+
+/// `size` groups of one to five people, who share some of their
+/// answers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let groups: Vec<String> = (0..size)
+        .map(|_| {
+            let shared: Vec<bool> = (0..26).map(|_| rng.chance(0.2)).collect();
+            let people = rng.range(1..6);
+            aoc_core::generate::lines(rng, people, |rng| {
+                let answers: String = (b'a'..=b'z')
+                    .zip(&shared)
+                    .filter(|&(_, &shared)| rng.chance(if shared { 0.9 } else { 0.1 }))
+                    .map(|(answer, _)| char::from(answer))
+                    .collect();
+                if answers.is_empty() {
+                    "a".to_string()
+                } else {
+                    answers
+                }
+            })
+        })
+        .collect();
+    groups.join("\n")
 }
 
-#[aoc(day6, part1)]
-fn part1(data: &[AnswerSheet]) -> usize {
+pub fn part1(data: &[AnswerSheet]) -> usize {
     data.iter().map(|sheet| sheet.union().len()).sum()
 }
 
-#[aoc(day6, part2)]
-fn part2(data: &[AnswerSheet]) -> usize {
+pub fn part2(data: &[AnswerSheet]) -> usize {
     data.iter().map(|sheet| sheet.intersection().len()).sum()
 }
@@ -1,40 +1,43 @@
 use crate::parsers::number;
+use aoc_core::{
+    graph::Graph,
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::alpha1,
-    combinator::{all_consuming, map, opt, recognize},
-    error::Error,
+    combinator::{map, opt, recognize},
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
-use std::collections::HashSet;
 
-type Node = (String, Vec<(u64, String)>);
+aoc_core::solution! {
+    type Input<'a> = Graph<&'a str, u64>;
+    type Part1 = usize;
+    type Part2 = u64;
+}
 
-fn parse_bag(input: &str) -> IResult<&str, String> {
-    map(
-        terminated(
-            recognize(separated_pair(alpha1, tag(" "), alpha1)),
-            terminated(tag(" bag"), opt(tag("s"))),
-        ),
-        |s: &str| s.to_string(),
+fn parse_bag(input: &str) -> IResult<&str, &str> {
+    terminated(
+        recognize(separated_pair(alpha1, tag(" "), alpha1)),
+        terminated(tag(" bag"), opt(tag("s"))),
     )(input)
 }
 
-fn parse_bag_count(input: &str) -> IResult<&str, (u64, String)> {
+fn parse_bag_count(input: &str) -> IResult<&str, (u64, &str)> {
     separated_pair(number, tag(" "), parse_bag)(input)
 }
 
-fn parse_bag_set(input: &str) -> IResult<&str, Vec<(u64, String)>> {
+fn parse_bag_set(input: &str) -> IResult<&str, Vec<(u64, &str)>> {
     alt((
         map(tag("no other bags"), |_| Default::default()),
         separated_list1(tag(", "), parse_bag_count),
     ))(input)
 }
 
-fn rule(input: &str) -> IResult<&str, (String, Vec<(u64, String)>)> {
+type Rule<'a> = (&'a str, Vec<(u64, &'a str)>);
+
+fn rule(input: &str) -> IResult<&str, Rule<'_>> {
     separated_pair(
         parse_bag,
         tag(" contain "),
@@ -42,78 +45,39 @@ fn rule(input: &str) -> IResult<&str, (String, Vec<(u64, String)>)> {
     )(input)
 }
 
-fn parse_rules(input: &str) -> IResult<&str, Vec<(String, Vec<(u64, String)>)>> {
+fn parse_rules(input: &str) -> IResult<&str, Vec<Rule<'_>>> {
     separated_list1(tag("\n"), rule)(input)
 }
 
-#[aoc_generator(day7)]
-fn parse_input(input: &str) -> Result<Vec<Node>, Error<String>> {
-    match all_consuming(parse_rules)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-}
-
-fn find_holders<'a>(data: &'a [Node], desc: &'a str) -> impl Iterator<Item = &'a String> + 'a {
-    data.iter().filter_map(move |(node, rules)| {
-        if rules.iter().any(|(_, rule)| desc == rule) {
-            Some(node)
-        } else {
-            None
+/// The bags, with an edge to each kind of bag they hold weighted by how
+/// many of them.
+pub fn parse_input(input: &str) -> Result<Graph<&str, u64>, ParseError> {
+    let rules = aoc_core::parse::all(input, terminated(parse_rules, tag("\n")))?;
+    let mut bags = Graph::directed();
+    for (bag, contents) in rules {
+        bags.add_node(bag);
+        for (count, inner) in contents {
+            bags.add_edge(bag, inner, count);
         }
-    })
-}
-
-#[aoc(day7, part1)]
-fn part1(data: &[Node]) -> usize {
-    let mut holders = find_holders(data, "shiny gold").collect::<HashSet<_>>();
-
-    let mut level = holders.clone();
-    loop {
-        let new_level = level
-            .into_iter()
-            .flat_map(|t| find_holders(data, t))
-            .collect::<HashSet<_>>();
-
-        if new_level.is_empty() {
-            break holders.len();
-        }
-
-        holders.extend(&new_level);
-        level = new_level;
     }
+    Ok(bags)
 }
 
-fn find_rules<'a>(data: &'a [Node], desc: &'a str) -> &'a [(u64, String)] {
-    data.iter()
-        .find_map(
-            |(node, rules)| {
-                if node == desc {
-                    Some(rules)
-                } else {
-                    None
-                }
-            },
-        )
-        .unwrap()
+pub fn part1(bags: &Graph<&str, u64>) -> usize {
+    let gold = bags.node("shiny gold").unwrap();
+    // Every bag but itself that it can be found in.
+    bags.reversed().dfs(gold).count() - 1
 }
 
-fn follow_rules(data: &[Node], rules: &[(u64, String)]) -> u64 {
-    rules
-        .iter()
-        .map(|(count, desc)| {
-            let rules = find_rules(data, desc);
-            let links = follow_rules(data, rules);
-            count + count * links
-        })
-        .sum()
-}
-
-#[aoc(day7, part2)]
-fn part2(data: &[Node]) -> u64 {
-    let rules = find_rules(data, "shiny gold");
-    follow_rules(data, rules)
+pub fn part2(bags: &Graph<&str, u64>) -> u64 {
+    // Count what's inside the innermost bags first, to count the bags
+    // holding them from.
+    let mut inside = vec![0; bags.len()];
+    for &bag in bags.toposort().unwrap().iter().rev() {
+        inside[bag] = bags
+            .edges(bag)
+            .map(|(inner, &count)| count * (1 + inside[inner]))
+            .sum();
+    }
+    inside[bags.node("shiny gold").unwrap()]
 }
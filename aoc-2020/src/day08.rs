@@ -1,16 +1,20 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::{alpha1, digit1, one_of},
-    combinator::{all_consuming, map, map_res, recognize},
-    error::Error,
+    combinator::{map_opt, map_res, recognize},
     multi::separated_list1,
-    sequence::{separated_pair, tuple},
-    Finish, IResult,
+    sequence::{separated_pair, terminated, tuple},
 };
 use std::{collections::HashSet, convert::TryInto};
 
-#[derive(Debug, Clone, Copy)]
-enum Op {
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = Option<i32>;
+    type Part2 = Option<i32>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
     Nop(i32),
     Acc(i32),
     Jmp(i32),
@@ -64,17 +68,17 @@ impl Machine {
 }
 
 fn op(input: &str) -> IResult<&str, Op> {
-    map(
+    map_opt(
         separated_pair(
             alpha1,
             tag(" "),
             map_res(recognize(tuple((one_of("+-"), digit1))), str::parse),
         ),
         |(op, arg)| match op {
-            "nop" => Op::Nop(arg),
-            "acc" => Op::Acc(arg),
-            "jmp" => Op::Jmp(arg),
-            _ => panic!(),
+            "nop" => Some(Op::Nop(arg)),
+            "acc" => Some(Op::Acc(arg)),
+            "jmp" => Some(Op::Jmp(arg)),
+            _ => None,
         },
     )(input)
 }
@@ -83,19 +87,11 @@ fn parse_program(input: &str) -> IResult<&str, Vec<Op>> {
     separated_list1(tag("\n"), op)(input)
 }
 
-#[aoc_generator(day8)]
-fn parse_input(input: &str) -> Result<Vec<Op>, Error<String>> {
-    match all_consuming(parse_program)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_program, tag("\n")))
 }
 
-#[aoc(day8, part1)]
-fn part1(data: &[Op]) -> Option<i32> {
+pub fn part1(data: &[Op]) -> Option<i32> {
     let mut machine = Machine::new(data.to_vec());
 
     loop {
@@ -107,8 +103,7 @@ fn part1(data: &[Op]) -> Option<i32> {
     }
 }
 
-#[aoc(day8, part2)]
-fn part2(data: &[Op]) -> Option<i32> {
+pub fn part2(data: &[Op]) -> Option<i32> {
     data.iter()
         .enumerate()
         .filter_map(|(idx, op)| {
@@ -136,3 +131,35 @@ fn part2(data: &[Op]) -> Option<i32> {
             }
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn program() -> impl Strategy<Value = Vec<Op>> {
+        let op = prop_oneof![
+            (-1000..1000).prop_map(Op::Nop),
+            (-1000..1000).prop_map(Op::Acc),
+            (-1000..1000).prop_map(Op::Jmp),
+        ];
+        prop::collection::vec(op, 1..30)
+    }
+
+    fn render(program: &[Op]) -> String {
+        program
+            .iter()
+            .map(|op| match op {
+                Op::Nop(arg) => format!("nop {:+}\n", arg),
+                Op::Acc(arg) => format!("acc {:+}\n", arg),
+                Op::Jmp(arg) => format!("jmp {:+}\n", arg),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(program, |program| render(program), parse_input);
+    }
+}
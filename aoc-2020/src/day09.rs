@@ -1,15 +1,23 @@
 use crate::iter::tails;
-use std::{cmp::Ordering, num::ParseIntError};
+use aoc_core::parse::ParseError;
+use std::cmp::Ordering;
+
+aoc_core::solution! {
+    type Input = Vec<u64>;
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+}
 
 const PREAMBLE_LEN: usize = 25;
 
-#[aoc_generator(day9)]
-fn parse_input(input: &str) -> Result<Vec<u64>, ParseIntError> {
-    input.lines().map(str::parse).collect()
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .lines()
+        .map(|line| aoc_core::parse::number(input, line))
+        .collect()
 }
 
-#[aoc(day9, part1)]
-fn part1(data: &[u64]) -> Option<u64> {
+pub fn part1(data: &[u64]) -> Option<u64> {
     data.windows(PREAMBLE_LEN + 1).find_map(|window| {
         let target = window[PREAMBLE_LEN];
 
@@ -24,8 +32,7 @@ fn part1(data: &[u64]) -> Option<u64> {
     })
 }
 
-#[aoc(day9, part2)]
-fn part2(data: &[u64]) -> Option<u64> {
+pub fn part2(data: &[u64]) -> Option<u64> {
     let weakness = part1(data)?;
 
     let mut start = 0;
@@ -1,10 +1,20 @@
-use std::{collections::HashSet, num::ParseIntError};
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+use std::collections::HashSet;
 
-#[aoc_generator(day10)]
-fn parse_input(input: &str) -> Result<Vec<u64>, ParseIntError> {
+aoc_core::solution! {
+    type Input = Vec<u64>;
+    type Part1 = usize;
+    type Part2 = u64;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
     let mut data = input
         .lines()
-        .map(str::parse)
+        .map(|line| aoc_core::parse::number(input, line))
         .collect::<Result<Vec<_>, _>>()?;
 
     data.push(0);
@@ -13,8 +23,28 @@ fn parse_input(input: &str) -> Result<Vec<u64>, ParseIntError> {
     Ok(data)
 }
 
-#[aoc(day10, part1)]
-fn part1_alt(data: &[u64]) -> usize {
+/// `size` adapters in a shuffled chain, one or three jolts apart like the
+/// puzzle's, in runs short enough to keep the count of arrangements in
+/// range.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut joltage = 0;
+    let mut adapters = Vec::with_capacity(size);
+    while adapters.len() < size {
+        joltage += 3;
+        adapters.push(joltage);
+        for _ in 0..rng.range(0..5) {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+    }
+    adapters.truncate(size);
+    rng.shuffle(&mut adapters);
+
+    let mut adapters = adapters.into_iter();
+    aoc_core::generate::lines(rng, size, |_| adapters.next().unwrap().to_string())
+}
+
+pub fn part1(data: &[u64]) -> usize {
     let mut jolt1 = 0;
     let mut jolt3 = 0;
 
@@ -30,12 +60,11 @@ fn part1_alt(data: &[u64]) -> usize {
     jolt1 * jolt3
 }
 
-#[aoc(day10, part2)]
-fn part2_dynamic(data: &[u64]) -> u64 {
+pub fn part2(data: &[u64]) -> u64 {
     let adapters: HashSet<u64> = data.iter().copied().collect();
 
     let max = *data.last().unwrap() as usize;
-    let mut combinations: Vec<u64> = vec![0; max as usize + 2];
+    let mut combinations: Vec<u64> = vec![0; max + 2];
     combinations[max] = 1;
 
     for idx in (0..max).rev() {
@@ -1,164 +1,118 @@
-use crate::parsers::grid;
-use nom::{
-    combinator::{all_consuming, map},
-    error::Error,
-    Finish, IResult,
+use aoc_core::{
+    grid::Grid,
+    parse::{tag, ParseError},
 };
+use nom::sequence::terminated;
 use std::{
     collections::hash_map::DefaultHasher,
-    convert::TryInto,
+    fmt,
     hash::{Hash, Hasher},
 };
 
-#[derive(Debug, PartialEq, Clone, Copy, Hash)]
-enum Cell {
+aoc_core::solution! {
+    type Input = Seats;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Cell {
     Floor,
     Empty,
     Occupied,
 }
 
-#[derive(Debug, Clone, Hash)]
-struct Grid {
-    width: i32,
-    height: i32,
-    cells: Vec<Cell>,
-}
-
-impl Grid {
-    fn get_index(&self, &(x, y): &(i32, i32)) -> usize {
-        (y * self.width + x).try_into().unwrap()
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Cell::Floor => '.',
+            Cell::Empty => 'L',
+            Cell::Occupied => '#',
+        };
+        write!(f, "{}", c)
     }
+}
 
-    fn get(&self, coord @ (x, y): &(i32, i32)) -> Option<&Cell> {
-        if (0..self.width).contains(x) && (0..self.height).contains(y) {
-            self.cells.get(self.get_index(coord))
-        } else {
-            None
-        }
+/// The 8 directions to look around a seat in.
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (0, 1),
+    (1, 1),
+    (1, -1),
+    (-1, 0),
+    (0, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Seats(Grid<Cell>);
+
+impl Seats {
+    fn neighbour_count(&self, x: usize, y: usize) -> u8 {
+        self.0
+            .neighbours8(x, y)
+            .filter(|&position| self.0[position] == Cell::Occupied)
+            .count() as u8
     }
 
-    fn neighbour_count(&self, x: i32, y: i32) -> u8 {
-        let mut count = 0;
-
-        for &delta_x in &[-1, 0, 1] {
-            for &delta_y in &[-1, 0, 1] {
-                if delta_x == 0 && delta_y == 0 {
-                    continue;
-                }
-
-                if let Some(Cell::Occupied) = self.get(&(x + delta_x, y + delta_y)) {
-                    count += 1;
+    fn neighbours_seen(&self, x: usize, y: usize) -> u8 {
+        let ray_trace = |(dx, dy)| {
+            let (mut x, mut y) = (x, y);
+            loop {
+                (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+                match self.0.get(x, y) {
+                    Some(Cell::Occupied) => return 1,
+                    Some(Cell::Empty) | None => return 0,
+                    Some(Cell::Floor) => {}
                 }
             }
-        }
-
-        count
-    }
-
-    fn neighbours_seen(&self, x: i32, y: i32) -> u8 {
-        let ray_trace = |coord| match self.get(&coord) {
-            Some(&Cell::Occupied) => Some(1),
-            Some(&Cell::Empty) => Some(0),
-            Some(&Cell::Floor) => None,
-            None => Some(0),
         };
 
-        [
-            (x + 1..).find_map(|x| ray_trace((x, y))).unwrap_or(0),
-            (y + 1..).find_map(|y| ray_trace((x, y))).unwrap_or(0),
-            (x + 1..)
-                .zip(y + 1..)
-                .find_map(|(x, y)| ray_trace((x, y)))
-                .unwrap_or(0),
-            (x + 1..)
-                .zip((0..y).rev())
-                .find_map(|(x, y)| ray_trace((x, y)))
-                .unwrap_or(0),
-            (0..x).rev().find_map(|x| ray_trace((x, y))).unwrap_or(0),
-            (0..y).rev().find_map(|y| ray_trace((x, y))).unwrap_or(0),
-            (0..x)
-                .rev()
-                .zip(y + 1..)
-                .find_map(|(x, y)| ray_trace((x, y)))
-                .unwrap_or(0),
-            (0..x)
-                .rev()
-                .zip((0..y).rev())
-                .find_map(|(x, y)| ray_trace((x, y)))
-                .unwrap_or(0),
-        ]
-        .iter()
-        .sum()
+        DIRECTIONS.into_iter().map(ray_trace).sum()
     }
 
     fn next_generation(&mut self) {
-        let mut next = self.cells.clone();
-
-        for x in 0..self.width {
-            for y in 0..self.height {
-                let cell = next.get_mut(self.get_index(&(x, y))).unwrap();
-                let neighbors = self.neighbour_count(x, y);
-
-                *cell = match (*cell, neighbors) {
-                    (Cell::Empty, 0) => Cell::Occupied,
-                    (Cell::Occupied, x) if x >= 4 => Cell::Empty,
-                    (otherwise, _) => otherwise,
-                };
+        let next = Grid::from_fn(self.0.width(), self.0.height(), |x, y| {
+            match (self.0[(x, y)], self.neighbour_count(x, y)) {
+                (Cell::Empty, 0) => Cell::Occupied,
+                (Cell::Occupied, x) if x >= 4 => Cell::Empty,
+                (otherwise, _) => otherwise,
             }
-        }
+        });
 
-        self.cells = next;
+        self.0 = next;
     }
 
     fn next_generation_with_ray_tracing(&mut self) {
-        let mut next = self.cells.clone();
-
-        for x in 0..self.width {
-            for y in 0..self.height {
-                let cell = next.get_mut(self.get_index(&(x, y))).unwrap();
-                let neighbors = self.neighbours_seen(x, y);
-
-                *cell = match (*cell, neighbors) {
-                    (Cell::Empty, 0) => Cell::Occupied,
-                    (Cell::Occupied, x) if x >= 5 => Cell::Empty,
-                    (otherwise, _) => otherwise,
-                };
+        let next = Grid::from_fn(self.0.width(), self.0.height(), |x, y| {
+            match (self.0[(x, y)], self.neighbours_seen(x, y)) {
+                (Cell::Empty, 0) => Cell::Occupied,
+                (Cell::Occupied, x) if x >= 5 => Cell::Empty,
+                (otherwise, _) => otherwise,
             }
-        }
+        });
 
-        self.cells = next;
+        self.0 = next;
     }
-}
 
-fn parse_grid(input: &str) -> IResult<&str, Grid> {
-    map(grid("#L."), |(grid, (width, height))| {
-        let cells = grid
-            .into_iter()
-            .map(|b| match b {
-                b'.' => Cell::Floor,
-                b'L' => Cell::Empty,
-                b'#' => Cell::Occupied,
-                _ => unreachable!(),
-            })
-            .collect();
-
-        Grid {
-            cells,
-            width: width as i32,
-            height: height as i32,
-        }
-    })(input)
+    fn occupied(&self) -> usize {
+        self.0
+            .cells()
+            .iter()
+            .filter(|&cell| cell == &Cell::Occupied)
+            .count()
+    }
 }
 
-#[aoc_generator(day11)]
-fn parse_input(input: &str) -> Result<Grid, Error<String>> {
-    match all_consuming(parse_grid)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Seats, ParseError> {
+    let seats = aoc_core::grid::parser(|c| match c {
+        '.' => Some(Cell::Floor),
+        'L' => Some(Cell::Empty),
+        '#' => Some(Cell::Occupied),
+        _ => None,
+    });
+    aoc_core::parse::all(input, terminated(seats, tag("\n"))).map(Seats)
 }
 
 fn calculate_hash<T: Hash>(t: &T) -> u64 {
@@ -167,42 +121,59 @@ fn calculate_hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
-#[aoc(day11, part1)]
-fn part1(grid: &Grid) -> usize {
-    let mut grid = grid.clone();
-    let mut hash = calculate_hash(&grid);
+pub fn part1(seats: &Seats) -> usize {
+    let mut seats = seats.clone();
+    let mut hash = calculate_hash(&seats);
 
     loop {
-        grid.next_generation();
-        let new_hash = calculate_hash(&grid);
+        seats.next_generation();
+        let new_hash = calculate_hash(&seats);
         if hash == new_hash {
             break;
         }
         hash = new_hash;
     }
 
-    grid.cells
-        .iter()
-        .filter(|&cell| cell == &Cell::Occupied)
-        .count()
+    seats.occupied()
 }
 
-#[aoc(day11, part2)]
-fn part2(grid: &Grid) -> usize {
-    let mut grid = grid.clone();
-    let mut hash = calculate_hash(&grid);
+pub fn part2(seats: &Seats) -> usize {
+    let mut seats = seats.clone();
+    let mut hash = calculate_hash(&seats);
 
     loop {
-        grid.next_generation_with_ray_tracing();
-        let new_hash = calculate_hash(&grid);
+        seats.next_generation_with_ray_tracing();
+        let new_hash = calculate_hash(&seats);
         if hash == new_hash {
             break;
         }
         hash = new_hash;
     }
 
-    grid.cells
-        .iter()
-        .filter(|&cell| cell == &Cell::Occupied)
-        .count()
+    seats.occupied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn seats() -> impl Strategy<Value = Seats> {
+        let cell = prop::sample::select(&[Cell::Floor, Cell::Empty, Cell::Occupied][..]);
+        (1..20usize, 1..20usize).prop_flat_map(move |(width, height)| {
+            prop::collection::vec(cell.clone(), width * height)
+                .prop_map(move |cells| Seats(Grid::new(cells, width)))
+        })
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(seats, |seats| seats.0.to_string(), parse_input);
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(parse_input("L.L\nLL\n").is_err());
+    }
 }
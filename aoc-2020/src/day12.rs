@@ -1,11 +1,24 @@
 use crate::parsers::number;
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag, character::complete::alpha1, combinator::all_consuming, error::Error,
-    multi::separated_list1, sequence::tuple, Finish, IResult,
+    character::complete::alpha1,
+    combinator::map_opt,
+    multi::separated_list1,
+    sequence::{terminated, tuple},
 };
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
+aoc_core::solution! {
+    type Input = Vec<Move>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 780 };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
     North,
     East,
     South,
@@ -52,12 +65,12 @@ impl Point {
     }
 
     fn distance(&self) -> usize {
-        self.x.abs() as usize + self.y.abs() as usize
+        (self.x.unsigned_abs() + self.y.unsigned_abs()) as usize
     }
 }
 
-#[derive(Debug)]
-enum Move {
+#[derive(Debug, PartialEq)]
+pub enum Move {
     Forward(u64),
     Direction(Direction, u64),
     Right(u64),
@@ -65,39 +78,39 @@ enum Move {
 }
 
 fn op(input: &str) -> IResult<&str, Move> {
-    let (input, (op, arg)) = tuple((alpha1, number))(input)?;
-
-    let op = match op {
-        "N" => Move::Direction(Direction::North, arg),
-        "E" => Move::Direction(Direction::East, arg),
-        "S" => Move::Direction(Direction::South, arg),
-        "W" => Move::Direction(Direction::West, arg),
-        "R" => Move::Right(arg),
-        "L" => Move::Left(arg),
-        "F" => Move::Forward(arg),
-        _ => panic!(),
-    };
-
-    Ok((input, op))
+    map_opt(tuple((alpha1, number)), |(op, arg)| match op {
+        "N" => Some(Move::Direction(Direction::North, arg)),
+        "E" => Some(Move::Direction(Direction::East, arg)),
+        "S" => Some(Move::Direction(Direction::South, arg)),
+        "W" => Some(Move::Direction(Direction::West, arg)),
+        "R" => Some(Move::Right(arg)),
+        "L" => Some(Move::Left(arg)),
+        "F" => Some(Move::Forward(arg)),
+        _ => None,
+    })(input)
 }
 
 fn parse_program(input: &str) -> IResult<&str, Vec<Move>> {
     separated_list1(tag("\n"), op)(input)
 }
 
-#[aoc_generator(day12)]
-fn parse_input(input: &str) -> Result<Vec<Move>, Error<String>> {
-    match all_consuming(parse_program)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_program, tag("\n")))
+}
+
+/// `size` navigation instructions, turning only by right angles.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        if rng.chance(0.15) {
+            format!("{}{}", rng.pick(&['L', 'R']), rng.pick(&[90, 180, 270]))
+        } else {
+            let action = rng.pick(&['N', 'E', 'S', 'W', 'F', 'F']);
+            format!("{}{}", action, rng.range(1..100))
+        }
+    })
 }
 
-#[aoc(day12, part1)]
-fn part1(data: &[Move]) -> usize {
+pub fn part1(data: &[Move]) -> usize {
     let mut position = Point::new(0, 0);
     let mut heading = Direction::East;
 
@@ -131,8 +144,7 @@ fn part1(data: &[Move]) -> usize {
     position.distance()
 }
 
-#[aoc(day12, part2)]
-fn part2(data: &[Move]) -> usize {
+pub fn part2(data: &[Move]) -> usize {
     let mut position = Point::new(0, 0);
     let mut waypoint = Point::new(10, 1);
 
@@ -163,3 +175,48 @@ fn part2(data: &[Move]) -> usize {
 
     position.distance()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn moves() -> impl Strategy<Value = Vec<Move>> {
+        let direction = prop::sample::select(
+            &[
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West,
+            ][..],
+        );
+        let step = prop_oneof![
+            (direction, 0..1000u64).prop_map(|(direction, arg)| Move::Direction(direction, arg)),
+            (0..1000u64).prop_map(Move::Forward),
+            (0..4u64).prop_map(|turns| Move::Right(turns * 90)),
+            (0..4u64).prop_map(|turns| Move::Left(turns * 90)),
+        ];
+        prop::collection::vec(step, 1..30)
+    }
+
+    fn render(moves: &[Move]) -> String {
+        moves
+            .iter()
+            .map(|step| match step {
+                Move::Direction(Direction::North, arg) => format!("N{}\n", arg),
+                Move::Direction(Direction::East, arg) => format!("E{}\n", arg),
+                Move::Direction(Direction::South, arg) => format!("S{}\n", arg),
+                Move::Direction(Direction::West, arg) => format!("W{}\n", arg),
+                Move::Right(arg) => format!("R{}\n", arg),
+                Move::Left(arg) => format!("L{}\n", arg),
+                Move::Forward(arg) => format!("F{}\n", arg),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(moves, |moves| render(moves), parse_input);
+    }
+}
@@ -1,34 +1,41 @@
-use std::num::ParseIntError;
+use crate::parsers::number;
+use aoc_core::{
+    numtheory,
+    parse::{tag, IResult, ParseError},
+};
+use nom::{
+    branch::alt,
+    combinator::map,
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+};
+
+aoc_core::solution! {
+    type Input = (u64, Vec<Bus>);
+    type Part1 = Option<u64>;
+    type Part2 = Option<u64>;
+    const PART2_VARIANTS = [part2_sieve];
+}
 
 #[derive(Debug, PartialEq)]
-enum Bus {
+pub enum Bus {
     Bus(u64),
     OutOfService,
 }
 
-#[aoc_generator(day13)]
-fn parse_input(input: &str) -> Result<(u64, Vec<Bus>), ParseIntError> {
-    let mut lines = input.lines();
+fn parse_bus(input: &str) -> IResult<&str, Bus> {
+    alt((map(tag("x"), |_| Bus::OutOfService), map(number, Bus::Bus)))(input)
+}
 
-    let timestamp = lines.next().unwrap().parse()?;
-    let schedule = lines
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|s| {
-            if s == "x" {
-                Ok(Bus::OutOfService)
-            } else {
-                Ok(Bus::Bus(s.parse()?))
-            }
-        })
-        .collect::<Result<_, _>>()?;
+fn parse_notes(input: &str) -> IResult<&str, (u64, Vec<Bus>)> {
+    separated_pair(number, tag("\n"), separated_list1(tag(","), parse_bus))(input)
+}
 
-    Ok((timestamp, schedule))
+pub fn parse_input(input: &str) -> Result<(u64, Vec<Bus>), ParseError> {
+    aoc_core::parse::all(input, terminated(parse_notes, tag("\n")))
 }
 
-#[aoc(day13, part1)]
-fn part1((start, schedule): &(u64, Vec<Bus>)) -> Option<u64> {
+pub fn part1((start, schedule): &(u64, Vec<Bus>)) -> Option<u64> {
     let schedule = schedule
         .iter()
         .filter_map(|entry| match entry {
@@ -45,26 +52,36 @@ fn part1((start, schedule): &(u64, Vec<Bus>)) -> Option<u64> {
     })
 }
 
-#[aoc(day13, part2)]
-fn part2_alt((_, schedule): &(u64, Vec<Bus>)) -> u64 {
-    let offsets = schedule
+/// Each bus, with how many minutes after the timestamp it should leave.
+fn offsets(schedule: &[Bus]) -> impl Iterator<Item = (u64, u64)> + '_ {
+    schedule
         .iter()
         .enumerate()
         .filter_map(|(idx, entry)| match entry {
             Bus::OutOfService => None,
-            Bus::Bus(id) => Some((idx as u64 % *id, *id)),
+            Bus::Bus(id) => Some((idx as u64, *id)),
         })
-        .collect::<Vec<_>>();
+}
+
+pub fn part2((_, schedule): &(u64, Vec<Bus>)) -> Option<u64> {
+    // The timestamp plus each offset is a multiple of that bus's id.
+    let congruences = offsets(schedule).map(|(offset, id)| ((id - offset % id) % id, id));
+    numtheory::crt(congruences).map(|(timestamp, _)| timestamp)
+}
 
+/// Line the buses up one at a time, stepping by the product of those
+/// lined up so far, which only works for ids that are coprime.
+pub fn part2_sieve((_, schedule): &(u64, Vec<Bus>)) -> Option<u64> {
     let mut timestamp = 0;
-    let (_, mut step) = offsets[0];
+    let mut step = 1;
 
-    for (offset, id) in &offsets[1..] {
-        while timestamp % id != id - offset {
+    for (offset, id) in offsets(schedule) {
+        let offset = offset % id;
+        while timestamp % id != (id - offset) % id {
             timestamp += step;
         }
         step *= id;
     }
 
-    timestamp
+    Some(timestamp)
 }
@@ -1,17 +1,22 @@
 use crate::parsers::number;
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while_m_n},
-    combinator::{all_consuming, map},
-    error::Error,
+    bytes::complete::take_while_m_n,
+    combinator::map,
     multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair},
-    Finish, IResult,
+    sequence::{delimited, preceded, separated_pair, terminated},
 };
 use std::collections::HashMap;
 
-#[derive(Debug)]
-enum Op {
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Op {
     SetMask(String),
     Write(u64, u64),
 }
@@ -38,15 +43,8 @@ fn parse_program(input: &str) -> IResult<&str, Vec<Op>> {
     separated_list1(tag("\n"), alt((parse_mask, parse_write)))(input)
 }
 
-#[aoc_generator(day14)]
-fn parse_input(input: &str) -> Result<Vec<Op>, Error<String>> {
-    match all_consuming(parse_program)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_program, tag("\n")))
 }
 
 struct DecoderV1 {
@@ -129,8 +127,7 @@ impl DecoderV2 {
     }
 }
 
-#[aoc(day14, part1)]
-fn part1(data: &[Op]) -> u64 {
+pub fn part1(data: &[Op]) -> u64 {
     let mut memory = HashMap::new();
     let mut decoder = None;
 
@@ -147,8 +144,7 @@ fn part1(data: &[Op]) -> u64 {
     memory.values().sum()
 }
 
-#[aoc(day14, part2)]
-fn part2(data: &[Op]) -> u64 {
+pub fn part2(data: &[Op]) -> u64 {
     let mut memory = HashMap::new();
     let mut decoder = None;
 
@@ -166,3 +162,33 @@ fn part2(data: &[Op]) -> u64 {
 
     memory.values().sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn program() -> impl Strategy<Value = Vec<Op>> {
+        let op = prop_oneof![
+            "[01X]{36}".prop_map(Op::SetMask),
+            (0..1u64 << 36, 0..1u64 << 36).prop_map(|(addr, value)| Op::Write(addr, value)),
+        ];
+        prop::collection::vec(op, 1..30)
+    }
+
+    fn render(program: &[Op]) -> String {
+        program
+            .iter()
+            .map(|op| match op {
+                Op::SetMask(mask) => format!("mask = {}\n", mask),
+                Op::Write(addr, value) => format!("mem[{}] = {}\n", addr, value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(program, |program| render(program), parse_input);
+    }
+}
@@ -1,8 +1,18 @@
-use std::num::ParseIntError;
+use crate::parsers::number;
+use aoc_core::parse::{tag, ParseError};
+use nom::{multi::separated_list1, sequence::terminated};
 
-#[aoc_generator(day15)]
-fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    input.split(',').map(str::parse).collect()
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    aoc_core::parse::all(
+        input,
+        terminated(separated_list1(tag(","), number), tag("\n")),
+    )
 }
 
 fn play(data: &[u32], target: u32) -> u32 {
@@ -26,12 +36,10 @@ fn play(data: &[u32], target: u32) -> u32 {
     })
 }
 
-#[aoc(day15, part1)]
-fn part1(data: &[u32]) -> u32 {
+pub fn part1(data: &[u32]) -> u32 {
     play(data, 2020)
 }
 
-#[aoc(day15, part2)]
-fn part2(data: &[u32]) -> u32 {
+pub fn part2(data: &[u32]) -> u32 {
     play(data, 30_000_000)
 }
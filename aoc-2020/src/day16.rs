@@ -1,22 +1,26 @@
 use crate::parsers::{number, range};
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::satisfy,
-    combinator::{all_consuming, map, recognize},
-    error::Error,
+    combinator::{map, recognize},
     multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair, tuple},
-    Finish, IResult,
+    sequence::{preceded, separated_pair, terminated, tuple},
 };
 use std::{
     collections::{HashMap, HashSet},
     ops::RangeInclusive,
 };
 
+aoc_core::solution! {
+    type Input = Document;
+    type Part1 = u32;
+    type Part2 = u64;
+}
+
 type Document = (Vec<Rule>, Vec<u32>, Vec<Vec<u32>>);
 
 #[derive(Debug)]
-struct Rule {
+pub struct Rule {
     name: String,
     range1: RangeInclusive<u32>,
     range2: RangeInclusive<u32>,
@@ -66,23 +70,15 @@ fn parse_data(input: &str) -> IResult<&str, Document> {
     ))(input)
 }
 
-#[aoc_generator(day16)]
-fn parse_input(input: &str) -> Result<Document, Error<String>> {
-    match all_consuming(parse_data)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Document, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_data, tag("\n")))
 }
 
 fn valid_fields(rules: &[Rule]) -> HashSet<u32> {
     rules.iter().flat_map(|rule| rule.iter_range()).collect()
 }
 
-#[aoc(day16, part1)]
-fn part1((rules, _, neighbours): &Document) -> u32 {
+pub fn part1((rules, _, neighbours): &Document) -> u32 {
     let valid = valid_fields(rules);
     neighbours
         .iter()
@@ -90,8 +86,7 @@ fn part1((rules, _, neighbours): &Document) -> u32 {
         .sum()
 }
 
-#[aoc(day16, part2)]
-fn part2((rules, ticket, neighbours): &Document) -> u64 {
+pub fn part2((rules, ticket, neighbours): &Document) -> u64 {
     let valid = valid_fields(rules);
     let valid_neighbours = neighbours
         .iter()
@@ -123,7 +118,7 @@ fn part2((rules, ticket, neighbours): &Document) -> u64 {
 
     loop {
         let unique = possibilities
-            .drain_filter(|_, set| set.len() == 1)
+            .extract_if(|_, set| set.len() == 1)
             .flat_map(|(key, unique_set)| {
                 unique_set
                     .into_iter()
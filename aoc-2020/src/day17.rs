@@ -1,57 +1,50 @@
-use crate::{conway, parsers::grid};
-use nom::{
-    combinator::{all_consuming, map},
-    error::Error,
-    Finish, IResult,
+use crate::conway;
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::{tag, ParseError},
 };
+use nom::sequence::terminated;
 use std::convert::TryFrom;
 
+aoc_core::solution! {
+    type Input = Grid<Cell>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 8 };
+}
+
 #[derive(Debug, PartialEq, Clone, Copy, Hash)]
-enum Cell {
+pub enum Cell {
     Inactive,
     Active,
 }
 
-type Grid = (Vec<Cell>, usize);
-
-fn parse_grid(input: &str) -> IResult<&str, Grid> {
-    map(grid("#."), |(grid, (width, _))| {
-        let cells = grid
-            .into_iter()
-            .map(|b| match b {
-                b'#' => Cell::Active,
-                b'.' => Cell::Inactive,
-                _ => unreachable!(),
-            })
-            .collect();
-
-        (cells, width)
-    })(input)
+pub fn parse_input(input: &str) -> Result<Grid<Cell>, ParseError> {
+    let grid = aoc_core::grid::parser(|c| match c {
+        '#' => Some(Cell::Active),
+        '.' => Some(Cell::Inactive),
+        _ => None,
+    });
+    aoc_core::parse::all(input, terminated(grid, tag("\n")))
 }
 
-#[aoc_generator(day17)]
-fn parse_input(input: &str) -> Result<Grid, Error<String>> {
-    match all_consuming(parse_grid)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+/// A `size` by `size` slice of cubes, about half of them active.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::grid(
+        rng,
+        size,
+        size,
+        |rng| if rng.chance(0.5) { '#' } else { '.' },
+    )
 }
 
-#[aoc(day17, part1)]
-fn part1((cells, width): &Grid) -> usize {
-    let board = cells
+pub fn part1(grid: &Grid<Cell>) -> usize {
+    let board = grid
         .iter()
-        .enumerate()
-        .filter_map(|(idx, cell)| {
+        .filter_map(|((x, y), cell)| {
             if cell == &Cell::Active {
-                Some((
-                    i32::try_from(idx % width).unwrap(),
-                    i32::try_from(idx / width).unwrap(),
-                    0i32,
-                ))
+                Some((i32::try_from(x).unwrap(), i32::try_from(y).unwrap(), 0i32))
             } else {
                 None
             }
@@ -61,16 +54,14 @@ fn part1((cells, width): &Grid) -> usize {
     conway::game_of_life(board, 6).alive_count()
 }
 
-#[aoc(day17, part2)]
-fn part2((cells, width): &Grid) -> usize {
-    let board = cells
+pub fn part2(grid: &Grid<Cell>) -> usize {
+    let board = grid
         .iter()
-        .enumerate()
-        .filter_map(|(idx, cell)| {
+        .filter_map(|((x, y), cell)| {
             if cell == &Cell::Active {
                 Some((
-                    i32::try_from(idx % width).unwrap(),
-                    i32::try_from(idx / width).unwrap(),
+                    i32::try_from(x).unwrap(),
+                    i32::try_from(y).unwrap(),
                     0i32,
                     0i32,
                 ))
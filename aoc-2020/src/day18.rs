@@ -1,15 +1,25 @@
 use crate::parsers::number;
+use aoc_core::parse::{tag, Error, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{one_of, space0},
     combinator::all_consuming,
-    error::Error,
     multi::{fold_many0, separated_list1},
-    sequence::{delimited, pair},
-    Finish, IResult,
+    sequence::{delimited, pair, terminated},
+    Finish,
 };
 
+aoc_core::solution! {
+    type Input<'a> = &'a str;
+    type Part1 = Result<i64, Error<String>>;
+    type Part2 = Result<i64, Error<String>>;
+}
+
+/// The homework is evaluated as it's parsed, which is up to each part.
+pub fn parse_input(input: &str) -> Result<&str, ParseError> {
+    Ok(input)
+}
+
 fn apply(op: char, a: i64, b: i64) -> i64 {
     match op {
         '+' => a + b,
@@ -20,8 +30,7 @@ fn apply(op: char, a: i64, b: i64) -> i64 {
     }
 }
 
-#[aoc(day18, part1)]
-fn part1(input: &str) -> Result<i64, Error<String>> {
+pub fn part1(input: &str) -> Result<i64, Error<String>> {
     fn parens(input: &str) -> IResult<&str, i64> {
         delimited(tag("("), expr, tag(")"))(input)
     }
@@ -40,17 +49,16 @@ fn part1(input: &str) -> Result<i64, Error<String>> {
         )(input)
     }
 
-    match all_consuming(separated_list1(tag("\n"), expr))(input).finish() {
+    match all_consuming(terminated(separated_list1(tag("\n"), expr), tag("\n")))(input).finish() {
         Ok((_, output)) => Ok(output.iter().sum()),
-        Err(Error { input, code }) => Err(Error {
+        Err(Error { input, expected }) => Err(Error {
             input: input.to_string(),
-            code,
+            expected,
         }),
     }
 }
 
-#[aoc(day18, part2)]
-fn part2(input: &str) -> Result<i64, Error<String>> {
+pub fn part2(input: &str) -> Result<i64, Error<String>> {
     fn parens(input: &str) -> IResult<&str, i64> {
         delimited(tag("("), expr, tag(")"))(input)
     }
@@ -79,11 +87,11 @@ fn part2(input: &str) -> Result<i64, Error<String>> {
         )(input)
     }
 
-    match all_consuming(separated_list1(tag("\n"), expr))(input).finish() {
+    match all_consuming(terminated(separated_list1(tag("\n"), expr), tag("\n")))(input).finish() {
         Ok((_, output)) => Ok(output.iter().sum()),
-        Err(Error { input, code }) => Err(Error {
+        Err(Error { input, expected }) => Err(Error {
             input: input.to_string(),
-            code,
+            expected,
         }),
     }
 }
@@ -1,18 +1,22 @@
 use crate::parsers::number;
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{alpha1, satisfy},
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::map,
     multi::separated_list1,
-    sequence::{delimited, separated_pair},
-    Finish, IResult,
+    sequence::{delimited, separated_pair, terminated},
 };
 use std::{collections::HashMap, iter::Peekable, str::Chars};
 
+aoc_core::solution! {
+    type Input = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 #[derive(Debug, Clone)]
-enum Rule {
+pub enum Rule {
     OneOf(Vec<Vec<u64>>),
     Match(char),
 }
@@ -52,20 +56,13 @@ fn parse_input2(input: &str) -> IResult<&str, Input> {
     )(input)
 }
 
-#[aoc_generator(day19)]
-fn parse_input(input: &str) -> Result<Input, Error<String>> {
-    match all_consuming(parse_input2)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Input, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_input2, tag("\n")))
 }
 
 fn match_rule(rules: &HashMap<u64, Rule>, rule: u64, chars: &mut Peekable<Chars>) -> bool {
     match rules.get(&rule) {
-        Some(Rule::Match(target)) => chars.next().map_or(false, |c| *target == c),
+        Some(Rule::Match(target)) => chars.next() == Some(*target),
         Some(Rule::OneOf(alts)) => alts.iter().any(|alt| {
             let mut recursing = false;
             let mut tmp_chars = chars.clone();
@@ -94,13 +91,11 @@ fn match_rules(rules: &HashMap<u64, Rule>, input: &str) -> bool {
     match_rule(rules, 0, &mut chars) && chars.peek().is_none()
 }
 
-#[aoc(day19, part1)]
-fn part1((rules, lines): &Input) -> usize {
+pub fn part1((rules, lines): &Input) -> usize {
     lines.iter().filter(|line| match_rules(rules, line)).count()
 }
 
-#[aoc(day19, part2)]
-fn part2((rules, lines): &Input) -> usize {
+pub fn part2((rules, lines): &Input) -> usize {
     let mut rules = rules.clone();
     rules.insert(8, Rule::OneOf(vec![vec![42], vec![42, 8]]));
     rules.insert(11, Rule::OneOf(vec![vec![42, 31], vec![42, 11, 31]]));
@@ -1,16 +1,20 @@
-use crate::parsers::{grid, number};
+use crate::parsers::number;
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::{map_opt, opt},
     multi::separated_list1,
-    sequence::{delimited, separated_pair},
-    Finish, IResult,
+    sequence::{delimited, pair, separated_pair, terminated},
 };
 use std::{collections::HashMap, convert::TryInto};
 
+aoc_core::solution! {
+    type Input = Vec<Tile>;
+    type Part1 = u64;
+    type Part2 = usize;
+}
+
 #[derive(Debug, Clone)]
-struct Tile {
+pub struct Tile {
     id: u32,
     data: [u8; 100],
 }
@@ -127,16 +131,16 @@ impl Transform {
 }
 
 fn tile(input: &str) -> IResult<&str, Tile> {
-    map(
+    map_opt(
         separated_pair(
             delimited(tag("Tile "), number, tag(":")),
             tag("\n"),
-            grid("#."),
+            aoc_core::grid::parser(|c| matches!(c, '#' | '.').then_some(c as u8)),
         ),
-        |(id, (grid, dim))| {
-            assert_eq!(dim, (10, 10));
-            let data = grid.try_into().unwrap();
-            Tile { id, data }
+        |(id, grid)| {
+            let width = grid.width();
+            let data = grid.into_cells().try_into().ok();
+            data.filter(|_| width == 10).map(|data| Tile { id, data })
         },
     )(input)
 }
@@ -145,15 +149,12 @@ fn parse_tiles(input: &str) -> IResult<&str, Vec<Tile>> {
     separated_list1(tag("\n\n"), tile)(input)
 }
 
-#[aoc_generator(day20)]
-fn parse_input(input: &str) -> Result<Vec<Tile>, Error<String>> {
-    match all_consuming(parse_tiles)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Tile>, ParseError> {
+    // Inputs come with a blank line after the last tile.
+    aoc_core::parse::all(
+        input,
+        terminated(parse_tiles, pair(tag("\n"), opt(tag("\n")))),
+    )
 }
 
 fn reverse_edge(value: u16) -> u16 {
@@ -181,14 +182,12 @@ fn iter_corners<'a>(
         .filter(move |tile| tile.edges().iter().map(|edge| edges[edge]).sum::<usize>() == 6)
 }
 
-#[aoc(day20, part1)]
-fn part1(tiles: &[Tile]) -> u64 {
+pub fn part1(tiles: &[Tile]) -> u64 {
     let edges = edge_map(tiles);
     iter_corners(&edges, tiles).fold(1, |acc, tile| acc * tile.id as u64)
 }
 
-#[aoc(day20, part2)]
-fn part2(tiles: &[Tile]) -> usize {
+pub fn part2(tiles: &[Tile]) -> usize {
     let width = (tiles.len() as f32).sqrt() as usize;
 
     let mut map = Vec::with_capacity(tiles.len());
@@ -1,16 +1,20 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::{alpha1, space1},
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::map,
     multi::separated_list1,
-    sequence::{delimited, separated_pair},
-    Finish, IResult,
+    sequence::{delimited, separated_pair, terminated},
 };
 use std::collections::{BTreeMap, HashMap, HashSet};
 
+aoc_core::solution! {
+    type Input = Vec<Food>;
+    type Part1 = usize;
+    type Part2 = String;
+}
+
 #[derive(Debug, Clone)]
-struct Food {
+pub struct Food {
     ingredients: HashSet<String>,
     warnings: HashSet<String>,
 }
@@ -41,15 +45,8 @@ fn parse_foods(input: &str) -> IResult<&str, Vec<Food>> {
     separated_list1(tag("\n"), parse_food)(input)
 }
 
-#[aoc_generator(day21)]
-fn parse_input(input: &str) -> Result<Vec<Food>, Error<String>> {
-    match all_consuming(parse_foods)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Food>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_foods, tag("\n")))
 }
 
 fn find_all_warnings(data: &[Food]) -> HashSet<String> {
@@ -59,8 +56,7 @@ fn find_all_warnings(data: &[Food]) -> HashSet<String> {
         .collect()
 }
 
-#[aoc(day21, part1)]
-fn part1(data: &[Food]) -> usize {
+pub fn part1(data: &[Food]) -> usize {
     let bad_ingredients = find_all_warnings(data)
         .into_iter()
         .filter_map(|warning| {
@@ -85,8 +81,7 @@ fn part1(data: &[Food]) -> usize {
         .count()
 }
 
-#[aoc(day21, part2)]
-fn part2(data: &[Food]) -> String {
+pub fn part2(data: &[Food]) -> String {
     let mut known = find_all_warnings(data)
         .into_iter()
         .filter_map(|warning| {
@@ -106,7 +101,7 @@ fn part2(data: &[Food]) -> String {
     let mut warnings = BTreeMap::new();
     loop {
         let unique = known
-            .drain_filter(|_, ingredients| ingredients.len() == 1)
+            .extract_if(|_, ingredients| ingredients.len() == 1)
             .flat_map(|(key, ingredients)| {
                 ingredients
                     .into_iter()
@@ -1,12 +1,9 @@
 use crate::parsers::number;
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::all_consuming,
-    error::Error,
     multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair},
-    Finish, IResult,
+    sequence::{delimited, preceded, separated_pair, terminated},
 };
 use std::{
     cmp::Ordering,
@@ -14,6 +11,12 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+aoc_core::solution! {
+    type Input = Game;
+    type Part1 = u32;
+    type Part2 = u32;
+}
+
 type Game = (Vec<u32>, Vec<u32>);
 
 fn player(input: &str) -> IResult<&str, Vec<u32>> {
@@ -27,15 +30,8 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     separated_pair(player, tag("\n\n"), player)(input)
 }
 
-#[aoc_generator(day22)]
-fn parse_input(input: &str) -> Result<Game, Error<String>> {
-    match all_consuming(parse_game)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Game, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_game, tag("\n")))
 }
 
 #[derive(Debug)]
@@ -60,13 +56,12 @@ fn combat(card1: u32, card2: u32) -> Player {
     }
 }
 
-#[aoc(day22, part1)]
-fn part1((deck1, deck2): &Game) -> u32 {
+pub fn part1((deck1, deck2): &Game) -> u32 {
     let mut deck1 = deck1.iter().copied().collect::<VecDeque<_>>();
     let mut deck2 = deck2.iter().copied().collect::<VecDeque<_>>();
 
     score_deck(&loop {
-        match (deck1.get(0), deck2.get(0)) {
+        match (deck1.front(), deck2.front()) {
             (None, _) => break deck2,
             (_, None) => break deck1,
             (Some(card1), Some(card2)) => match combat(*card1, *card2) {
@@ -98,7 +93,7 @@ fn recursive_combat(mut deck1: VecDeque<u32>, mut deck2: VecDeque<u32>) -> (Play
             break (Player::Player1, deck1);
         }
 
-        match (deck1.get(0), deck2.get(0)) {
+        match (deck1.front(), deck2.front()) {
             (None, _) => break (Player::Player2, deck2),
             (_, None) => break (Player::Player1, deck1),
             (Some(card1), Some(card2)) => {
@@ -130,8 +125,7 @@ fn recursive_combat(mut deck1: VecDeque<u32>, mut deck2: VecDeque<u32>) -> (Play
     }
 }
 
-#[aoc(day22, part2)]
-fn part2((deck1, deck2): &Game) -> u32 {
+pub fn part2((deck1, deck2): &Game) -> u32 {
     score_deck(
         &recursive_combat(
             deck1.iter().copied().collect(),
@@ -1,4 +1,10 @@
-use std::num::ParseIntError;
+use aoc_core::parse::ParseError;
+
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = String;
+    type Part2 = u64;
+}
 
 fn prev_cup(cup: u32, max: u32) -> u32 {
     if cup == 1 {
@@ -8,13 +14,13 @@ fn prev_cup(cup: u32, max: u32) -> u32 {
     }
 }
 
-#[aoc_generator(day23)]
-fn parse_input(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    input.chars().map(|c| c.to_string().parse()).collect()
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    aoc_core::parse::cells(input, "cup", |c| {
+        (b'1'..=b'9').contains(&c).then(|| (c - b'0') as u32)
+    })
 }
 
-#[aoc(day23, part1)]
-fn part1(data: &[u32]) -> String {
+pub fn part1(data: &[u32]) -> String {
     let mut turn: Vec<u32> = data.to_vec();
 
     for _ in 0..100 {
@@ -22,7 +28,7 @@ fn part1(data: &[u32]) -> String {
         let next = &turn[1..=3];
 
         let mut pivot = prev_cup(start, 9);
-        while next.iter().any(|&c| c == pivot) {
+        while next.contains(&pivot) {
             pivot = prev_cup(pivot, 9);
         }
 
@@ -53,8 +59,7 @@ fn cup_to_addr(cup: u32) -> usize {
     (cup - 1) as usize
 }
 
-#[aoc(day23, part2)]
-fn part2(data: &[u32]) -> u64 {
+pub fn part2(data: &[u32]) -> u64 {
     const MAX_CUP: u32 = 1_000_000;
 
     let start_addr = cup_to_addr(*data.first().unwrap());
@@ -64,7 +69,7 @@ fn part2(data: &[u32]) -> u64 {
         .map(|window| (window[0], cup_to_addr(window[1])))
         .collect::<Vec<_>>();
     cup_links.push((*data.last().unwrap(), data.len()));
-    cup_links.sort_by(|(cup1, _), (cup2, _)| cup1.cmp(cup2));
+    cup_links.sort_by_key(|&(cup, _)| cup);
 
     let mut links: Vec<usize> = Vec::with_capacity(MAX_CUP as usize);
     links.extend(cup_links.into_iter().map(|(_, addr)| addr));
@@ -83,7 +88,7 @@ fn part2(data: &[u32]) -> u64 {
             addr_to_cup(cup3_addr),
         ];
         let mut pivot = prev_cup(addr_to_cup(position), MAX_CUP);
-        while next.iter().any(|&c| c == pivot) {
+        while next.contains(&pivot) {
             pivot = prev_cup(pivot, MAX_CUP);
         }
 
@@ -97,7 +102,8 @@ fn part2(data: &[u32]) -> u64 {
         position = next_addr;
     }
 
-    let first_addr = cup_to_addr(1);
+    // The two cups right after cup 1.
+    let first_addr = links[cup_to_addr(1)];
     let second_addr = links[first_addr];
     addr_to_cup(first_addr) as u64 * addr_to_cup(second_addr) as u64
 }
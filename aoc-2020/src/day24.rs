@@ -1,16 +1,25 @@
 use crate::conway::{self, Neighbors};
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::map,
     multi::{many1, separated_list1},
-    Finish, IResult,
+    sequence::terminated,
 };
 use std::collections::HashMap;
 
+aoc_core::solution! {
+    type Input = Vec<Vec<Direction>>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 300 };
+}
+
 #[derive(Debug, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     East,
     SouthEast,
     SouthWest,
@@ -19,9 +28,10 @@ enum Direction {
     NorthEast,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Hash)]
+#[derive(Debug, Default, PartialEq, Clone, Copy, Hash)]
 enum Tile {
     Black,
+    #[default]
     White,
 }
 
@@ -34,12 +44,6 @@ impl Tile {
     }
 }
 
-impl Default for Tile {
-    fn default() -> Self {
-        Tile::White
-    }
-}
-
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 struct Axial(i32, i32);
 
@@ -72,15 +76,18 @@ fn parse_directions(input: &str) -> IResult<&str, Vec<Vec<Direction>>> {
     separated_list1(tag("\n"), many1(direction))(input)
 }
 
-#[aoc_generator(day24)]
-fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>, Error<String>> {
-    match all_consuming(parse_directions)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_directions, tag("\n")))
+}
+
+/// `size` paths to tiles near the reference tile.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let steps = rng.range(12..25);
+        (0..steps)
+            .map(|_| *rng.pick(&["e", "se", "sw", "w", "nw", "ne"]))
+            .collect()
+    })
 }
 
 fn lay_tiles(data: &[Vec<Direction>]) -> HashMap<Axial, Tile> {
@@ -96,8 +103,7 @@ fn lay_tiles(data: &[Vec<Direction>]) -> HashMap<Axial, Tile> {
     map
 }
 
-#[aoc(day24, part1)]
-fn part1(data: &[Vec<Direction>]) -> usize {
+pub fn part1(data: &[Vec<Direction>]) -> usize {
     lay_tiles(data)
         .into_values()
         .filter(|&tile| tile == Tile::Black)
@@ -121,8 +127,7 @@ impl Neighbors for Axial {
     }
 }
 
-#[aoc(day24, part2)]
-fn part2(data: &[Vec<Direction>]) -> usize {
+pub fn part2(data: &[Vec<Direction>]) -> usize {
     let board = lay_tiles(data)
         .into_iter()
         .filter_map(|(point, tile)| {
@@ -1,10 +1,15 @@
 use crate::parsers::number;
-use nom::{
-    bytes::complete::tag, combinator::all_consuming, error::Error, sequence::separated_pair,
-    Finish, IResult,
+use aoc_core::{
+    numtheory,
+    parse::{tag, IResult, ParseError},
 };
+use nom::sequence::{separated_pair, terminated};
 
-const MAX_ITERATIONS: usize = 1_000_000_000;
+aoc_core::solution! {
+    type Input = (u64, u64);
+    type Part1 = Option<u64>;
+    type Part2 = &'static str;
+}
 
 const SHARED_BASE: u64 = 7;
 const SHARED_MOD: u64 = 20201227;
@@ -13,40 +18,16 @@ fn parse_keys(input: &str) -> IResult<&str, (u64, u64)> {
     separated_pair(number, tag("\n"), number)(input)
 }
 
-#[aoc_generator(day25)]
-fn parse_input(input: &str) -> Result<(u64, u64), Error<String>> {
-    match all_consuming(parse_keys)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-}
-
-fn transform(subject: u64) -> impl Iterator<Item = u64> {
-    let mut value = 1;
-    std::iter::from_fn(move || {
-        value = (value * subject) % SHARED_MOD;
-        Some(value)
-    })
+pub fn parse_input(input: &str) -> Result<(u64, u64), ParseError> {
+    aoc_core::parse::all(input, terminated(parse_keys, tag("\n")))
 }
 
-fn crack(target: u64, iterations: usize) -> Option<usize> {
-    transform(SHARED_BASE)
-        .take(iterations)
-        .enumerate()
-        .find_map(|(count, value)| {
-            if value == target {
-                Some(count + 1)
-            } else {
-                None
-            }
-        })
+pub fn part1(&(card_key, door_key): &(u64, u64)) -> Option<u64> {
+    let loop_size = numtheory::discrete_log(SHARED_BASE, card_key, SHARED_MOD)?;
+    Some(numtheory::modpow(door_key, loop_size, SHARED_MOD))
 }
 
-#[aoc(day25, part1)]
-fn part1(&(card_key, door_key): &(u64, u64)) -> Option<u64> {
-    let loop_size = crack(card_key, MAX_ITERATIONS).unwrap();
-    transform(door_key).nth(loop_size - 1)
+/// There's no puzzle to part 2, its star comes with all the others.
+pub fn part2(_: &(u64, u64)) -> &'static str {
+    "⭐"
 }
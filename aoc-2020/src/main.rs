@@ -1,3 +1,65 @@
-use aoc_runner_derive::aoc_main;
+#[cfg(test)]
+#[macro_use]
+extern crate maplit;
 
-aoc_main! { lib = advent_of_code_2020 }
+use aoc_core::{days, Year};
+
+aoc_core::counting_allocator!();
+
+mod conway;
+mod iter;
+mod parsers;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+fn year() -> Year {
+    Year {
+        year: 2020,
+        days: days![
+            day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+            day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24,
+            day25,
+        ],
+    }
+}
+
+fn main() {
+    aoc_core::run(year());
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generated_inputs_parse() {
+        aoc_core::generate::check(&super::year());
+    }
+}
@@ -1,5 +1,29 @@
-pub fn parse_input(input: &str) -> Vec<u32> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 2000 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .lines()
+        .map(|line| aoc_core::parse::number(input, line))
+        .collect()
+}
+
+/// `size` depths of a descending sea floor, with the odd rise.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..200u32);
+    aoc_core::generate::lines(rng, size, |rng| {
+        depth = depth.saturating_add_signed(rng.range(-10..30));
+        depth.to_string()
+    })
 }
 
 fn decent_step_count<const N: usize>(data: &[u32]) -> usize {
@@ -1,3 +1,15 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
 #[derive(Debug)]
 pub enum Direction {
     Forward,
@@ -8,24 +20,32 @@ pub enum Direction {
 #[derive(Debug)]
 pub struct Op(Direction, u32);
 
-pub fn parse_input(input: &str) -> Vec<Op> {
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let (direction, step) = line.split_once(' ').unwrap();
-            Op(
-                match direction {
-                    "forward" => Direction::Forward,
-                    "up" => Direction::Up,
-                    "down" => Direction::Down,
-                    _ => unreachable!(),
-                },
-                step.parse().unwrap(),
-            )
+            let (direction, step) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::new(input, line, "direction and step"))?;
+            let direction = match direction {
+                "forward" => Direction::Forward,
+                "up" => Direction::Up,
+                "down" => Direction::Down,
+                _ => return Err(ParseError::new(input, direction, "forward, up or down")),
+            };
+            Ok(Op(direction, aoc_core::parse::number(input, step)?))
         })
         .collect()
 }
 
+/// `size` commands.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let direction = rng.pick(&["forward", "forward", "up", "down", "down"]);
+        format!("{} {}", direction, rng.range(1..10))
+    })
+}
+
 pub fn part1(input: &[Op]) -> u32 {
     let mut horizontal: u32 = 0;
     let mut depth: u32 = 0;
@@ -1,17 +1,67 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Report;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
+/// The width of the generated numbers, as in the real reports.
 const BITWIDTH: usize = 12;
-const MASK: u16 = (1 << BITWIDTH) - 1;
 
-pub fn parse_input(input: &str) -> Vec<u16> {
-    input
+/// The diagnostic numbers, all `width` bits wide.
+pub struct Report {
+    width: usize,
+    numbers: Vec<u16>,
+}
+
+pub fn parse_input(input: &str) -> Result<Report, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 || width > 16 {
+        return Err(ParseError::new(
+            input,
+            input,
+            "binary numbers up to 16 bits",
+        ));
+    }
+
+    let numbers = input
         .lines()
-        .map(|line| u16::from_str_radix(line, 2).unwrap())
-        .collect()
+        .map(|line| {
+            if line.len() != width {
+                return Err(ParseError::new(
+                    input,
+                    line,
+                    format!("{} bit binary number", width),
+                ));
+            }
+            u16::from_str_radix(line, 2).map_err(|_| ParseError::new(input, line, "binary number"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Report { width, numbers })
 }
 
-pub fn part1(input: &[u16]) -> u32 {
-    let mut counts = [0usize; BITWIDTH];
+/// `size` diagnostic numbers, all different as long as there's enough
+/// of them to go around.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u16> = (0..1 << BITWIDTH).collect();
+    rng.shuffle(&mut numbers);
+
+    let mut numbers = numbers.into_iter().cycle();
+    aoc_core::generate::lines(rng, size, |_| {
+        format!("{:0width$b}", numbers.next().unwrap(), width = BITWIDTH)
+    })
+}
 
-    for num in input {
+pub fn part1(report: &Report) -> u32 {
+    let mut counts = vec![0usize; report.width];
+
+    for num in &report.numbers {
         for (shift, count) in counts.iter_mut().enumerate() {
             if num & 1 << shift != 0 {
                 *count += 1;
@@ -22,16 +72,27 @@ pub fn part1(input: &[u16]) -> u32 {
     let gamma = counts
         .iter()
         .rev()
-        .map(|count| if count * 2 > input.len() { 1 } else { 0 })
-        .fold(0u16, |gamma, bit| gamma << 1 | bit);
+        .map(|count| {
+            if count * 2 > report.numbers.len() {
+                1
+            } else {
+                0
+            }
+        })
+        .fold(0u32, |gamma, bit| gamma << 1 | bit);
 
-    gamma as u32 * (!gamma & MASK) as u32
+    gamma * (!gamma & ((1 << report.width) - 1))
 }
 
-fn scan(mut candidates: Vec<u16>, prefer_ones: bool) -> Option<u16> {
-    for shift in 0..BITWIDTH {
-        let mask = 1 << (BITWIDTH - shift - 1);
+fn scan(report: &Report, prefer_ones: bool) -> Option<u16> {
+    let mut candidates = report.numbers.clone();
+    for shift in 0..report.width {
+        let mask = 1 << (report.width - shift - 1);
         let count = candidates.iter().filter(|&num| num & mask == 0).count();
+        if count == 0 || count == candidates.len() {
+            // Every candidate has the same bit, there's no least common one.
+            continue;
+        }
 
         let expect_one = prefer_ones ^ (count * 2 <= candidates.len());
         candidates.retain(|&num| expect_one ^ (num & mask == 0));
@@ -44,9 +105,9 @@ fn scan(mut candidates: Vec<u16>, prefer_ones: bool) -> Option<u16> {
     candidates.pop()
 }
 
-pub fn part2(input: &[u16]) -> u32 {
-    let oxygen = scan(input.to_vec(), true).unwrap();
-    let co2 = scan(input.to_vec(), false).unwrap();
+pub fn part2(report: &Report) -> u32 {
+    let oxygen = scan(report, true).unwrap();
+    let co2 = scan(report, false).unwrap();
 
     oxygen as u32 * co2 as u32
 }
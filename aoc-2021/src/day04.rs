@@ -1,17 +1,25 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt},
-    error::Error,
+    combinator::{map, map_res, opt, verify},
     multi::separated_list1,
     sequence::{preceded, separated_pair, terminated},
-    Finish, IResult,
 };
 use std::{num::NonZeroU32, str::FromStr};
 
+aoc_core::solution! {
+    type Input = Game;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 type Board = [u16; 25];
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Game {
     pub calls: Vec<u16>,
     pub boards: Vec<Board>,
@@ -43,7 +51,7 @@ impl BitBoard {
             0b1000010000100001000010000,
         ];
 
-        PATTERNS.iter().any(|&pattern| self.0 & pattern == pattern)
+        PATTERNS.iter().any(|&pattern| pattern & !self.0 == 0)
     }
 
     fn score(&self, board: &Board) -> u32 {
@@ -78,12 +86,17 @@ fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     preceded(opt(tag(" ")), map_res(digit1, FromStr::from_str))(input)
 }
 
+/// Board numbers are below 100, for `BoardMap`.
+fn cell(input: &str) -> IResult<&str, u16> {
+    verify(number, |&number| number < 100)(input)
+}
+
 fn board(input: &str) -> IResult<&str, Board> {
-    map(
-        separated_list1(tag("\n"), separated_list1(tag(" "), number)),
+    map_res(
+        separated_list1(tag("\n"), separated_list1(tag(" "), cell)),
         |numbers: Vec<Vec<u16>>| {
             let numbers: Vec<u16> = numbers.iter().flatten().copied().collect();
-            numbers.try_into().unwrap()
+            Board::try_from(numbers)
         },
     )(input)
 }
@@ -106,15 +119,27 @@ fn parse_file(input: &str) -> IResult<&str, Game> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Game {
-    match all_consuming(parse_file)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
+pub fn parse_input(input: &str) -> Result<Game, ParseError> {
+    aoc_core::parse::all(input, parse_file)
+}
+
+/// All the numbers called in a random order, and `size` boards.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u16> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let calls: Vec<String> = numbers.iter().map(u16::to_string).collect();
+
+    let mut out = calls.join(",") + "\n";
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        out.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|number| format!("{:>2}", number)).collect();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
     }
-    .unwrap()
+    out
 }
 
 fn simulate_game(board: &Board, calls: &[u16], limit: usize) -> Option<(usize, u32)> {
@@ -137,7 +162,7 @@ fn simulate_game(board: &Board, calls: &[u16], limit: usize) -> Option<(usize, u
 }
 
 pub fn part1(Game { calls, boards }: &Game) -> u32 {
-    let mut limit = boards.len();
+    let mut limit = calls.len();
 
     let (_, score) = boards
         .iter()
@@ -155,9 +180,48 @@ pub fn part1(Game { calls, boards }: &Game) -> u32 {
 pub fn part2(Game { calls, boards }: &Game) -> u32 {
     let (_, score) = boards
         .iter()
-        .flat_map(|board| simulate_game(board, calls, boards.len()))
+        .flat_map(|board| simulate_game(board, calls, calls.len()))
         .max_by_key(|&(generation, _)| generation)
         .unwrap();
 
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn game() -> impl Strategy<Value = Game> {
+        (
+            prop::collection::vec(0..1000u16, 1..30),
+            prop::collection::vec(prop::array::uniform25(0..100u16), 1..4),
+        )
+            .prop_map(|(calls, boards)| Game { calls, boards })
+    }
+
+    fn render(game: &Game) -> String {
+        let calls: Vec<String> = game.calls.iter().map(u16::to_string).collect();
+        let boards: Vec<String> = game
+            .boards
+            .iter()
+            .map(|board| {
+                let rows: Vec<String> = board
+                    .chunks(5)
+                    .map(|row| {
+                        let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+                        row.join(" ")
+                    })
+                    .collect();
+                rows.join("\n")
+            })
+            .collect();
+        format!("{}\n\n{}\n", calls.join(","), boards.join("\n\n"))
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(game, render, parse_input);
+    }
+}
@@ -1,15 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt},
-    error::Error,
+    combinator::{map, map_res, opt},
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
 use std::{ops::Range, str::FromStr};
 
-#[derive(Debug)]
+aoc_core::solution! {
+    type Input = Vec<Segment>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 500 };
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Point {
     x: i32,
     y: i32,
@@ -21,7 +29,7 @@ impl Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Segment(Point, Point);
 
 impl Segment {
@@ -120,15 +128,34 @@ fn parse_file(input: &str) -> IResult<&str, Vec<Segment>> {
     terminated(separated_list1(tag("\n"), parse_line), opt(tag("\n")))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Segment> {
-    match all_consuming(parse_file)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Segment>, ParseError> {
+    aoc_core::parse::all(input, parse_file)
+}
+
+/// `size` horizontal, vertical or diagonal lines of vents, on the
+/// puzzle's 1000 by 1000 floor.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let (x1, y1) = (rng.range(0..1000i32), rng.range(0..1000i32));
+        let length = rng.range(1..1000i32);
+        let (dx, dy) = *rng.pick(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+        let (dx, dy) = if rng.chance(0.5) {
+            (dx, dy)
+        } else {
+            (-dx, -dy)
+        };
+
+        // Shorten the line to keep it on the floor.
+        let fits = |start: i32, step: i32| match step {
+            1 => 999 - start,
+            -1 => start,
+            _ => i32::MAX,
+        };
+        let length = length.min(fits(x1, dx)).min(fits(y1, dy)).max(1);
+        let (x2, y2) = (x1 + dx * length, y1 + dy * length);
+        let (x2, y2) = (x2.clamp(0, 999), y2.clamp(0, 999));
+        format!("{},{} -> {},{}", x1, y1, x2, y2)
+    })
 }
 
 fn count_points_of_interest(points: impl Iterator<Item = Point>) -> usize {
@@ -160,3 +187,30 @@ pub fn part1(input: &[Segment]) -> usize {
 pub fn part2(input: &[Segment]) -> usize {
     count_points_of_interest(input.iter().flat_map(|segment| segment.interpolate()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn segments() -> impl Strategy<Value = Vec<Segment>> {
+        let point = (0..1000, 0..1000).prop_map(|(x, y)| Point::new(x, y));
+        prop::collection::vec(
+            (point.clone(), point).prop_map(|(a, b)| Segment::new(a, b)),
+            1..30,
+        )
+    }
+
+    fn render(segments: &[Segment]) -> String {
+        segments
+            .iter()
+            .map(|Segment(a, b)| format!("{},{} -> {},{}\n", a.x, a.y, b.x, b.y))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(segments, |segments| render(segments), parse_input);
+    }
+}
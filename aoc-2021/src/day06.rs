@@ -1,11 +1,29 @@
-pub fn parse_input(input: &str) -> Vec<u8> {
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 300 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
     input
         .lines()
         .flat_map(|line| line.split(','))
-        .map(|segment| segment.parse().unwrap())
+        .map(|segment| aoc_core::parse::number(input, segment))
         .collect()
 }
 
+/// `size` lanternfish, with their usual timers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let fish: Vec<String> = (0..size).map(|_| rng.range(1..6).to_string()).collect();
+    fish.join(",") + "\n"
+}
+
 fn simulate<const N: usize>(data: &[u8]) -> usize {
     let mut tank = [0; 9];
     for &fish in data {
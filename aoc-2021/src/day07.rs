@@ -1,17 +1,33 @@
-pub fn parse_input(input: &str) -> Vec<u32> {
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
     input
         .lines()
         .flat_map(|line| line.split(','))
-        .map(|segment| segment.parse().unwrap())
+        .map(|segment| aoc_core::parse::number(input, segment))
         .collect()
 }
 
+/// `size` crabs, bunched up towards the start.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let crabs: Vec<String> = (0..size)
+        .map(|_| (rng.range(0..2000u32) * rng.range(0..2000u32) / 2000).to_string())
+        .collect();
+    crabs.join(",") + "\n"
+}
+
 fn linear_cost(a: u32, b: u32) -> u32 {
-    if a > b {
-        a - b
-    } else {
-        b - a
-    }
+    a.abs_diff(b)
 }
 
 fn triangle_cost(a: u32, b: u32) -> u32 {
@@ -43,6 +59,11 @@ pub fn part1(input: &[u32]) -> u32 {
 }
 
 pub fn part2(input: &[u32]) -> u32 {
+    // The best position is within half a step of the mean, which could
+    // round either way.
     let target = mean(input);
-    get_fuel_consumption(input, target, triangle_cost)
+    (target..=target + 1)
+        .map(|target| get_fuel_consumption(input, target, triangle_cost))
+        .min()
+        .unwrap()
 }
@@ -1,3 +1,15 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 200 };
+}
+
 type Pattern = u8;
 
 #[derive(Debug)]
@@ -6,41 +18,63 @@ pub struct Entry {
     output: Vec<Pattern>,
 }
 
-fn parse_segments(input: &str) -> Vec<Pattern> {
-    input
+fn parse_segments(input: &str, segments: &str) -> Result<Vec<Pattern>, ParseError> {
+    segments
         .split(' ')
         .map(|segment| {
             segment
-                .as_bytes()
-                .iter()
-                .map(|b| match b {
-                    b'a' => 1 << 0,
-                    b'b' => 1 << 1,
-                    b'c' => 1 << 2,
-                    b'd' => 1 << 3,
-                    b'e' => 1 << 4,
-                    b'f' => 1 << 5,
-                    b'g' => 1 << 6,
-                    _ => unreachable!(),
+                .bytes()
+                .enumerate()
+                .try_fold(0, |pattern, (idx, b)| match b {
+                    b'a'..=b'g' => Ok(pattern | 1 << (b - b'a')),
+                    _ => Err(ParseError::new(input, &segment[idx..], "segment a-g")),
                 })
-                .sum()
         })
         .collect()
 }
 
-pub fn parse_input(input: &str) -> Vec<Entry> {
+pub fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let (signal, output) = line.split_once(" | ").unwrap();
-            Entry {
-                signal: parse_segments(signal),
-                output: parse_segments(output),
-            }
+            let (signal, output) = line
+                .split_once(" | ")
+                .ok_or_else(|| ParseError::new(input, line, "signal and output"))?;
+            Ok(Entry {
+                signal: parse_segments(input, signal)?,
+                output: parse_segments(input, output)?,
+            })
         })
         .collect()
 }
 
+/// `size` displays, each wired up differently.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+
+    aoc_core::generate::lines(rng, size, |rng| {
+        let mut wires: Vec<u8> = (b'a'..=b'g').collect();
+        rng.shuffle(&mut wires);
+        let rewire = |rng: &mut Rng, digit: &str| {
+            let mut segments: Vec<u8> = digit.bytes().map(|b| wires[(b - b'a') as usize]).collect();
+            rng.shuffle(&mut segments);
+            String::from_utf8(segments).unwrap()
+        };
+
+        let mut signal: Vec<String> = DIGITS.iter().map(|digit| rewire(rng, digit)).collect();
+        rng.shuffle(&mut signal);
+        let output: Vec<String> = (0..4)
+            .map(|_| {
+                let digit = *rng.pick(&DIGITS);
+                rewire(rng, digit)
+            })
+            .collect();
+        format!("{} | {}", signal.join(" "), output.join(" "))
+    })
+}
+
 pub fn part1(input: &[Entry]) -> usize {
     input
         .iter()
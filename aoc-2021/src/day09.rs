@@ -1,99 +1,59 @@
-use std::{collections::BinaryHeap, num::NonZeroUsize};
-
-#[derive(Debug)]
-pub struct Grid {
-    data: Vec<u8>,
-    width: usize,
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::ParseError,
+};
+use std::collections::BinaryHeap;
+
+aoc_core::solution! {
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 100 };
 }
 
-impl Grid {
-    fn new(map: &[u8], width: usize) -> Self {
-        let height = map.len() / width;
-
-        let new_width = width + 2;
-        let new_height = height + 2;
-        let mut new_map = vec![9; new_width * new_height];
-
-        let offset = new_width + 1;
-        for (line, chunk) in map.chunks(width).enumerate() {
-            let pos = line * new_width + offset;
-            new_map[pos..pos + width].copy_from_slice(chunk);
-        }
-
-        Self {
-            data: new_map,
-            width: new_width,
-        }
-    }
-
-    fn neighbours(&self, pos: usize) -> impl Iterator<Item = (usize, u8)> + '_ {
-        [pos - 1, pos + 1, pos + self.width, pos - self.width]
-            .into_iter()
-            .flat_map(|pos| self.data.get(pos).map(|&cell| (pos, cell)))
-    }
+pub fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    aoc_core::parse::grid(input, "digit", |c| c.is_ascii_digit().then(|| c - b'0'))
 }
 
-pub fn parse_input(input: &str) -> Grid {
-    let mut width: Option<NonZeroUsize> = None;
-    let grid: Vec<u8> = input
-        .lines()
-        .flat_map(|line| {
-            if width.is_none() {
-                width = NonZeroUsize::new(line.len());
-            }
-
-            line.chars().map(|c| c.to_digit(10).unwrap() as u8)
-        })
-        .collect();
-
-    Grid::new(&grid, width.unwrap().get())
+/// A `size` by `size` height map.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::grid(rng, size, size, |rng| char::from(b'0' + rng.range(0..10)))
 }
 
-pub fn part1(grid: &Grid) -> usize {
-    grid.data
-        .iter()
-        .enumerate()
-        .filter_map(|(pos, &cell)| {
-            (cell != 9 && grid.neighbours(pos).all(|(_, neighbour)| neighbour > cell))
-                .then(|| cell as usize + 1)
-        })
+pub fn part1(grid: &Grid<u8>) -> usize {
+    grid.iter()
+        .filter(|&((x, y), &cell)| grid.neighbours4(x, y).all(|position| grid[position] > cell))
+        .map(|(_, &cell)| cell as usize + 1)
         .sum()
 }
 
-pub fn part2(grid: &Grid) -> usize {
+pub fn part2(grid: &Grid<u8>) -> usize {
+    let mut visited = grid.map(|&cell| cell == 9);
+    let mut stack = Vec::with_capacity(100);
+
     let mut basins: BinaryHeap<_> = grid
-        .data
-        .iter()
-        .enumerate()
-        .filter(|&(_, cell)| *cell != 9)
-        .scan(
-            (vec![false; grid.data.len()], Vec::with_capacity(100)),
-            |(visited, stack), (pos, _)| {
-                if visited[pos] {
-                    Some(0)
-                } else {
-                    stack.push(pos);
+        .positions()
+        .filter_map(|start| {
+            if visited[start] {
+                return None;
+            }
+            stack.push(start);
 
-                    let mut basin = 0;
-                    while let Some(pos) = stack.pop() {
-                        if visited[pos] {
-                            continue;
-                        }
+            let mut basin = 0;
+            while let Some(position) = stack.pop() {
+                if visited[position] {
+                    continue;
+                }
 
-                        visited[pos] = true;
-                        basin += 1;
+                visited[position] = true;
+                basin += 1;
 
-                        stack.extend(
-                            grid.neighbours(pos)
-                                .filter_map(|(neighbour, cell)| (cell != 9).then(|| neighbour)),
-                        );
-                    }
+                stack.extend(grid.neighbours4(position.0, position.1));
+            }
 
-                    Some(basin)
-                }
-            },
-        )
-        .filter(|&basin| basin > 0)
+            Some(basin)
+        })
         .collect();
 
     (0..3).flat_map(|_| basins.pop()).product()
@@ -1,5 +1,62 @@
-pub fn parse_input(input: &str) -> Vec<&[u8]> {
-    input.lines().map(|line| line.as_bytes()).collect()
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input<'a> = Vec<&'a [u8]>;
+    type Part1 = usize;
+    type Part2 = u64;
+    const GENERATOR = Generator { generate, size: 94 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    input
+        .lines()
+        .map(|line| match line.find(|c| !"()[]{}<>".contains(c)) {
+            Some(idx) => Err(ParseError::new(input, &line[idx..], "bracket")),
+            None => Ok(line.as_bytes()),
+        })
+        .collect()
+}
+
+/// `size` lines of navigation subsystem, about half of them corrupted and
+/// the rest incomplete, of which there's an odd number.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const PAIRS: [(u8, u8); 4] = [(b'(', b')'), (b'[', b']'), (b'{', b'}'), (b'<', b'>')];
+
+    let incomplete = (size / 2) | 1;
+    let mut kinds: Vec<bool> = (0..size).map(|idx| idx < incomplete).collect();
+    rng.shuffle(&mut kinds);
+
+    let mut kinds = kinds.into_iter();
+    aoc_core::generate::lines(rng, size, |rng| {
+        let corrupt = !kinds.next().unwrap();
+        let length = rng.range(90..110);
+        let mut line = Vec::with_capacity(length);
+        let mut stack = Vec::new();
+        while line.len() < length || stack.is_empty() {
+            // Keep chunks shallow, completion scores grow fivefold with
+            // every level.
+            if stack.is_empty() || (stack.len() < 12 && rng.chance(0.55)) {
+                let (open, close) = *rng.pick(&PAIRS);
+                line.push(open);
+                stack.push(close);
+            } else {
+                line.push(stack.pop().unwrap());
+            }
+        }
+        if corrupt {
+            let expected = stack.pop().unwrap();
+            let wrong: Vec<u8> = PAIRS
+                .iter()
+                .map(|&(_, close)| close)
+                .filter(|&close| close != expected)
+                .collect();
+            line.push(*rng.pick(&wrong));
+        }
+        String::from_utf8(line).unwrap()
+    })
 }
 
 #[derive(Debug)]
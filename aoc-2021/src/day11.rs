@@ -1,15 +1,16 @@
+use aoc_core::parse::ParseError;
 use std::iter;
 
+aoc_core::solution! {
+    type Input = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 const WIDTH: u32 = 10;
 
-pub fn parse_input(input: &str) -> Vec<u8> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.chars()
-                .map(|char| u8::try_from(char.to_digit(10).unwrap()).unwrap())
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    aoc_core::parse::cells(input, "digit", |c| c.is_ascii_digit().then(|| c - b'0'))
 }
 
 struct Map {
@@ -43,15 +44,13 @@ fn neighbours(point: u32) -> [Option<u32>; 8] {
             .checked_sub(1)
             .filter(|p| p % WIDTH != WIDTH - 1)
             .and_then(|p| p.checked_add(WIDTH))
-            .filter(|&p| p < (WIDTH * WIDTH) as u32),
-        point
-            .checked_add(WIDTH)
-            .filter(|&p| p < (WIDTH * WIDTH) as u32),
+            .filter(|&p| p < WIDTH * WIDTH),
+        point.checked_add(WIDTH).filter(|&p| p < WIDTH * WIDTH),
         point
             .checked_add(1)
             .filter(|p| p % WIDTH != 0)
             .and_then(|p| p.checked_add(WIDTH))
-            .filter(|&p| p < (WIDTH * WIDTH) as u32),
+            .filter(|&p| p < WIDTH * WIDTH),
     ]
 }
 
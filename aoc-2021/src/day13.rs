@@ -1,13 +1,21 @@
+use aoc_core::{
+    ocr,
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{anychar, digit1},
-    combinator::{all_consuming, map, map_res, opt},
-    error::Error,
+    branch::alt,
+    character::complete::digit1,
+    combinator::{map, map_res, opt},
     multi::separated_list1,
     sequence::{preceded, separated_pair, terminated},
-    Finish, IResult,
 };
-use std::{collections::HashSet, fmt::Write, str::FromStr};
+use std::{collections::HashSet, str::FromStr};
+
+aoc_core::solution! {
+    type Input = (Vec<Point>, Vec<Fold>);
+    type Part1 = usize;
+    type Part2 = Result<String, ocr::Error>;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
@@ -15,7 +23,7 @@ pub struct Point {
     y: i32,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Fold {
     Right(usize),
     Up(usize),
@@ -40,14 +48,10 @@ fn parse_point(input: &str) -> IResult<&str, Point> {
 fn parse_fold(input: &str) -> IResult<&str, Fold> {
     preceded(
         tag("fold along "),
-        map(
-            separated_pair(anychar, tag("="), number),
-            |(axis, pos)| match axis {
-                'x' => Fold::Right(pos),
-                'y' => Fold::Up(pos),
-                _ => unreachable!(),
-            },
-        ),
+        alt((
+            map(preceded(tag("x="), number), Fold::Right),
+            map(preceded(tag("y="), number), Fold::Up),
+        )),
     )(input)
 }
 
@@ -66,15 +70,8 @@ fn parse_file(input: &str) -> IResult<&str, (Vec<Point>, Vec<Fold>)> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> (Vec<Point>, Vec<Fold>) {
-    match all_consuming(parse_file)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<(Vec<Point>, Vec<Fold>), ParseError> {
+    aoc_core::parse::all(input, parse_file)
 }
 
 fn fold_board(input: &HashSet<Point>, fold: &Fold) -> HashSet<Point> {
@@ -107,32 +104,49 @@ pub fn part1((points, folds): &(Vec<Point>, Vec<Fold>)) -> usize {
     fold_board(&board, &folds[0]).len()
 }
 
-fn dump(board: &HashSet<Point>) -> String {
-    let max_x = board.iter().map(|p| p.x).max().unwrap() + 1;
-    let max_y = board.iter().map(|p| p.y).max().unwrap() + 1;
-    let mut output = String::with_capacity(max_x as usize * max_y as usize);
-
-    for y in 0..max_y {
-        for x in 0..max_x {
-            let p = Point { x, y };
-            if board.contains(&p) {
-                write!(&mut output, "█").unwrap();
-            } else {
-                write!(&mut output, "░").unwrap();
-            }
-        }
-        writeln!(&mut output).unwrap();
-    }
-    output
-}
-
-pub fn part2((points, folds): &(Vec<Point>, Vec<Fold>)) -> usize {
+pub fn part2((points, folds): &(Vec<Point>, Vec<Fold>)) -> Result<String, ocr::Error> {
     let mut board: HashSet<Point> = points.iter().copied().collect();
     for fold in folds {
         board = fold_board(&board, fold);
     }
+    ocr::read_points(board.iter().map(|p| (p.x as i64, p.y as i64)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn manual() -> impl Strategy<Value = (Vec<Point>, Vec<Fold>)> {
+        let point = (0..2000, 0..2000).prop_map(|(x, y)| Point::new(x, y));
+        let fold = prop_oneof![
+            (0..1000usize).prop_map(Fold::Right),
+            (0..1000usize).prop_map(Fold::Up),
+        ];
+        (
+            prop::collection::vec(point, 1..30),
+            prop::collection::vec(fold, 1..10),
+        )
+    }
 
-    // FIXME: simplistic runner can't handle strings yet
-    print!("{}", dump(&board));
-    0
+    fn render((points, folds): &(Vec<Point>, Vec<Fold>)) -> String {
+        let points: String = points
+            .iter()
+            .map(|point| format!("{},{}\n", point.x, point.y))
+            .collect();
+        let folds: String = folds
+            .iter()
+            .map(|fold| match fold {
+                Fold::Right(x) => format!("fold along x={}\n", x),
+                Fold::Up(y) => format!("fold along y={}\n", y),
+            })
+            .collect();
+        format!("{}\n{}", points, folds)
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(manual, render, parse_input);
+    }
 }
@@ -1,12 +1,22 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::{tag, take_while},
-    combinator::{all_consuming, map, opt},
-    error::Error,
+    bytes::complete::take_while1,
+    character::complete::satisfy,
+    combinator::{map, opt},
     multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    Finish, IResult,
+    sequence::{pair, separated_pair, terminated},
 };
 
+aoc_core::solution! {
+    type Input = Manual;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 20 };
+}
+
 fn index(c: u8) -> usize {
     (c - b'A') as usize
 }
@@ -17,20 +27,24 @@ fn index_pair(a: u8, b: u8) -> usize {
 
 fn word(input: &str) -> IResult<&str, Vec<u8>> {
     map(
-        take_while(move |c: char| c.is_ascii_uppercase()),
+        take_while1(move |c: char| c.is_ascii_uppercase()),
         |word: &str| word.bytes().collect(),
     )(input)
 }
 
+fn element(input: &str) -> IResult<&str, usize> {
+    map(satisfy(|c| c.is_ascii_uppercase()), |c| index(c as u8))(input)
+}
+
 fn parse_rule(input: &str) -> IResult<&str, (usize, usize, usize)> {
-    map(separated_pair(word, tag(" -> "), word), |(pair, result)| {
-        let a = index(pair[0]);
-        let b = index(pair[1]);
-        let c = index(result[0]);
-        (a * 26 + b, a * 26 + c, c * 26 + b)
-    })(input)
+    map(
+        separated_pair(pair(element, element), tag(" -> "), element),
+        |((a, b), c)| (a * 26 + b, a * 26 + c, c * 26 + b),
+    )(input)
 }
 
+type Manual = (Vec<u8>, Vec<(usize, usize, usize)>);
+
 fn parse_template(input: &str) -> IResult<&str, Vec<u8>> {
     word(input)
 }
@@ -39,22 +53,30 @@ fn parse_rules(input: &str) -> IResult<&str, Vec<(usize, usize, usize)>> {
     separated_list1(tag("\n"), parse_rule)(input)
 }
 
-fn parse_file(input: &str) -> IResult<&str, (Vec<u8>, Vec<(usize, usize, usize)>)> {
+fn parse_file(input: &str) -> IResult<&str, Manual> {
     terminated(
         separated_pair(parse_template, tag("\n\n"), parse_rules),
         opt(tag("\n")),
     )(input)
 }
 
-pub fn parse_input(input: &str) -> (Vec<u8>, Vec<(usize, usize, usize)>) {
-    match all_consuming(parse_file)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
+pub fn parse_input(input: &str) -> Result<Manual, ParseError> {
+    aoc_core::parse::all(input, parse_file)
+}
+
+/// A template of `size` elements, and an insertion rule for every pair.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const ELEMENTS: &[u8] = b"BCFHKNOPSV";
+
+    let template: Vec<u8> = (0..size).map(|_| *rng.pick(ELEMENTS)).collect();
+    let mut out = String::from_utf8(template).unwrap() + "\n\n";
+    for &a in ELEMENTS {
+        for &b in ELEMENTS {
+            let c = *rng.pick(ELEMENTS);
+            out.push_str(&format!("{}{} -> {}\n", a as char, b as char, c as char));
+        }
     }
-    .unwrap()
+    out
 }
 
 struct Polymer<'a> {
@@ -101,17 +123,19 @@ fn solve<const N: usize>(template: &[u8], rules: &[(usize, usize, usize)]) -> us
     for (pair, count) in polymer.iter().enumerate() {
         counts[pair % 26] += count;
     }
-    counts[index(template[0])] = 1;
+    // Only the second element of every pair was counted, which misses
+    // the very first one.
+    counts[index(template[0])] += 1;
 
     let max = counts.iter().max().unwrap();
     let min = counts.iter().filter(|&&count| count > 0).min().unwrap();
     max - min
 }
 
-pub fn part1(input: &(Vec<u8>, Vec<(usize, usize, usize)>)) -> usize {
+pub fn part1(input: &Manual) -> usize {
     solve::<10>(&input.0, &input.1)
 }
 
-pub fn part2(input: &(Vec<u8>, Vec<(usize, usize, usize)>)) -> usize {
+pub fn part2(input: &Manual) -> usize {
     solve::<40>(&input.0, &input.1)
 }
@@ -1,92 +1,81 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
-
-const WIDTH: usize = 100;
-
-type NodeIdx = usize;
-
-pub fn parse_input(input: &str) -> Vec<u8> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.chars()
-                .map(|char| u8::try_from(char.to_digit(10).unwrap()).unwrap())
-        })
-        .collect()
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::ParseError,
+    pathfinding::{self, Graph},
+};
+
+aoc_core::solution! {
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 100 };
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct State(NodeIdx, usize);
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+pub fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    aoc_core::parse::grid(input, "digit", |c| c.is_ascii_digit().then(|| c - b'0'))
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.1.cmp(&self.1)
-    }
+/// A `size` by `size` map of risk levels.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::grid(rng, size, size, |rng| char::from(b'0' + rng.range(1..10)))
 }
 
-fn neighbours(point: usize, width: usize) -> [Option<usize>; 4] {
-    [
-        point.checked_sub(width),
-        point.checked_sub(1).filter(|p| p % width != width - 1),
-        point.checked_add(1).filter(|p| p % width != 0),
-        point.checked_add(width).filter(|&p| p < width * width),
-    ]
-}
+/// The cavern's risk levels, as a graph of the spots in it, numbered
+/// row by row, with the risk of entering each as the cost to get there.
+struct Cavern<'a>(&'a Grid<u8>);
 
-fn djikstra(start: NodeIdx, goal: NodeIdx, board: &[u8], width: usize) -> usize {
-    let mut heap = BinaryHeap::new();
-    heap.push(State(start, 0));
+impl Graph for Cavern<'_> {
+    type Node = usize;
+    type Cost = usize;
 
-    let mut costs = vec![0; board.len()];
-    while let Some(State(index, cost)) = heap.pop() {
-        if index == goal {
-            break;
+    fn neighbours<F: FnMut(usize, usize)>(&self, node: usize, mut edge: F) {
+        let (width, risks) = (self.0.width(), self.0.cells());
+        let mut enter = |next: usize| edge(next, risks[next] as usize);
+        let (x, y) = self.0.position_of(node);
+        if y > 0 {
+            enter(node - width);
+        }
+        if x > 0 {
+            enter(node - 1);
         }
+        if x + 1 < width {
+            enter(node + 1);
+        }
+        if y + 1 < self.0.height() {
+            enter(node + width);
+        }
+    }
 
-        heap.extend(
-            neighbours(index, width)
-                .iter()
-                .flatten()
-                .filter_map(|&next| {
-                    let new_cost = cost + board[next] as usize;
-                    let current_cost = costs[next];
-                    (current_cost == 0 || new_cost < current_cost).then(|| {
-                        costs[next] = new_cost;
-                        State(next, new_cost)
-                    })
-                }),
-        );
+    fn node_count(&self) -> Option<usize> {
+        Some(self.0.cells().len())
     }
 
-    costs[goal]
+    fn node_index(&self, node: usize) -> usize {
+        node
+    }
 }
 
-fn solve(board: &[u8], width: usize) -> usize {
-    djikstra(0, board.len() - 1, board, width)
+fn solve(board: &Grid<u8>) -> usize {
+    let goal = board.cells().len() - 1;
+    let cavern = Cavern(board);
+    let (_, risk) = pathfinding::dijkstra(&cavern, [0], |node| node == goal)
+        .target()
+        .unwrap();
+    risk
 }
 
-pub fn part1(input: &[u8]) -> usize {
-    solve(input, WIDTH)
+pub fn part1(input: &Grid<u8>) -> usize {
+    solve(input)
 }
 
-pub fn part2(input: &[u8]) -> usize {
-    let mut board = Vec::with_capacity(input.len() * 25);
-
-    for y in 0..WIDTH * 5 {
-        for x in 0..WIDTH * 5 {
-            let source = x % WIDTH + (y % WIDTH) * WIDTH;
-            let factor = x / WIDTH + y / WIDTH;
-
-            let v = input[source] + factor as u8;
-            let v = (v - 1) % 9 + 1;
-            board.push(v);
-        }
-    }
+pub fn part2(input: &Grid<u8>) -> usize {
+    let (width, height) = (input.width(), input.height());
+    let board = Grid::from_fn(width * 5, height * 5, |x, y| {
+        let factor = x / width + y / height;
+        let v = input[(x % width, y % height)] + factor as u8;
+        (v - 1) % 9 + 1
+    });
 
-    solve(&board, WIDTH * 5)
+    solve(&board)
 }
@@ -1,3 +1,11 @@
+use aoc_core::parse::ParseError;
+
+aoc_core::solution! {
+    type Input = Packet;
+    type Part1 = u32;
+    type Part2 = u64;
+}
+
 struct Reader<'a> {
     input: &'a [u8],
     pos: usize,
@@ -18,9 +26,9 @@ impl<'a> Reader<'a> {
         self.bit = 3;
     }
 
-    fn read_bit(&mut self) -> u8 {
+    fn read_bit(&mut self) -> Option<u8> {
         let mask = 1 << self.bit;
-        let out = (self.input[self.pos] & mask) >> self.bit;
+        let out = (self.input.get(self.pos)? & mask) >> self.bit;
 
         if self.bit == 0 {
             self.flush();
@@ -28,16 +36,17 @@ impl<'a> Reader<'a> {
             self.bit -= 1;
         }
 
-        out
+        Some(out)
     }
 
-    fn read_byte(&mut self) -> u8 {
-        let out = self.input[self.pos];
+    fn read_byte(&mut self) -> Option<u8> {
+        let out = *self.input.get(self.pos)?;
         self.flush();
-        out
+        Some(out)
     }
 
-    fn read(&mut self, size: usize) -> u32 {
+    /// Read `size` bits, or `None` if the transmission ends first.
+    fn read(&mut self, size: usize) -> Option<u32> {
         if size < 4 {
             self.read_small(size)
         } else {
@@ -45,38 +54,39 @@ impl<'a> Reader<'a> {
         }
     }
 
-    fn read_small(&mut self, size: usize) -> u32 {
+    fn read_small(&mut self, size: usize) -> Option<u32> {
         let mut out = 0;
         for _ in 0..size {
             out <<= 1;
-            out |= self.read_bit() as u32
+            out |= self.read_bit()? as u32
         }
-        out
+        Some(out)
     }
 
-    fn read_large(&mut self, mut size: usize) -> u32 {
+    fn read_large(&mut self, mut size: usize) -> Option<u32> {
         let mut out = 0;
+        let byte = *self.input.get(self.pos)? as u32;
 
         // read til 4 byte aligned
         match self.bit {
             0 => {
                 out <<= 1;
-                out |= self.input[self.pos] as u32 & 0b0001;
+                out |= byte & 0b0001;
                 size -= 1;
             }
             1 => {
                 out <<= 2;
-                out |= self.input[self.pos] as u32 & 0b0011;
+                out |= byte & 0b0011;
                 size -= 2;
             }
             2 => {
                 out <<= 3;
-                out |= self.input[self.pos] as u32 & 0b0111;
+                out |= byte & 0b0111;
                 size -= 3;
             }
             3 => {
                 out <<= 4;
-                out |= self.input[self.pos] as u32;
+                out |= byte;
                 size -= 4;
             }
             _ => unreachable!(),
@@ -86,16 +96,16 @@ impl<'a> Reader<'a> {
         // read aligned
         for _ in 0..(size / 4) {
             out <<= 4;
-            out |= self.read_byte() as u32;
+            out |= self.read_byte()? as u32;
         }
 
         // read leftover
         for _ in 0..(size % 4) {
             out <<= 1;
-            out |= self.read_bit() as u32
+            out |= self.read_bit()? as u32
         }
 
-        out
+        Some(out)
     }
 }
 
@@ -122,54 +132,61 @@ pub struct Packet {
     body: Body,
 }
 
-fn parse_literal(reader: &mut Reader) -> (Body, usize) {
+fn parse_literal(reader: &mut Reader) -> Option<(Body, usize)> {
     let mut read = 0;
     let mut value = 0;
 
     loop {
-        let mark = reader.read(1);
+        let mark = reader.read(1)?;
         value <<= 4;
-        value |= reader.read(4) as u64;
+        value |= reader.read(4)? as u64;
         read += 5;
 
         if mark == 0 {
-            break (Body::Literal(value), read);
+            break Some((Body::Literal(value), read));
         }
     }
 }
 
-fn parse_form(op: Op, reader: &mut Reader) -> (Body, usize) {
-    let length_encoding = reader.read(1);
-    if length_encoding == 1 {
-        let chunks = reader.read(11);
+fn parse_form(op: Op, reader: &mut Reader) -> Option<(Body, usize)> {
+    let length_encoding = reader.read(1)?;
+    let (packets, read) = if length_encoding == 1 {
+        let chunks = reader.read(11)?;
         let mut read = 0;
         let mut packets = Vec::with_capacity(chunks as usize);
 
         for _ in 0..chunks {
-            let (packet, size) = parse_packet(reader);
+            let (packet, size) = parse_packet(reader)?;
             read += size;
             packets.push(packet);
         }
 
-        (Body::Form(op, packets), 12 + read)
+        (packets, 12 + read)
     } else {
-        let length = reader.read(15) as usize;
+        let length = reader.read(15)? as usize;
         let mut read = 0usize;
         let mut packets = Vec::new();
 
         while read < length {
-            let (packet, size) = parse_packet(reader);
+            let (packet, size) = parse_packet(reader)?;
             read += size;
             packets.push(packet);
         }
 
-        (Body::Form(op, packets), 16 + read)
-    }
+        (packets, 16 + read)
+    };
+
+    // Comparisons take exactly two packets, the others at least one.
+    let arity_ok = match op {
+        Op::GreaterThan | Op::LessThan | Op::Equal => packets.len() == 2,
+        _ => !packets.is_empty(),
+    };
+    arity_ok.then_some((Body::Form(op, packets), read))
 }
 
-fn parse_packet(reader: &mut Reader) -> (Packet, usize) {
-    let version = reader.read(3);
-    let op = reader.read(3);
+fn parse_packet(reader: &mut Reader) -> Option<(Packet, usize)> {
+    let version = reader.read(3)?;
+    let op = reader.read(3)?;
 
     let mut read = 6;
     let (body, size) = match op {
@@ -182,17 +199,16 @@ fn parse_packet(reader: &mut Reader) -> (Packet, usize) {
         6 => parse_form(Op::LessThan, reader),
         7 => parse_form(Op::Equal, reader),
         _ => unreachable!(),
-    };
+    }?;
     read += size;
 
-    (Packet { version, body }, read)
+    Some((Packet { version, body }, read))
 }
 
 impl Packet {
-    fn parse(bytes: &[u8]) -> Self {
+    fn parse(bytes: &[u8]) -> Option<Self> {
         let mut reader = Reader::new(bytes);
-        let (packet, _) = parse_packet(&mut reader);
-        packet
+        parse_packet(&mut reader).map(|(packet, _)| packet)
     }
 
     fn sum_versions(&self) -> u32 {
@@ -235,16 +251,13 @@ impl Packet {
     }
 }
 
-pub fn parse_input(input: &str) -> Packet {
-    let bytes: Vec<u8> = input
-        .lines()
-        .flat_map(|line| {
-            line.chars()
-                .map(|b| u8::try_from(b.to_digit(16).unwrap()).unwrap())
-        })
-        .collect();
+pub fn parse_input(input: &str) -> Result<Packet, ParseError> {
+    let bytes = aoc_core::parse::cells(input, "hexadecimal digit", |b| {
+        (b as char).to_digit(16).map(|digit| digit as u8)
+    })?;
 
     Packet::parse(&bytes)
+        .ok_or_else(|| ParseError::new(input, &input[input.trim_end().len()..], "complete packet"))
 }
 
 pub fn part1(packet: &Packet) -> u32 {
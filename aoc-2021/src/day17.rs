@@ -1,13 +1,17 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt, recognize},
-    error::Error,
+    combinator::{map, map_res, opt, recognize},
     sequence::{preceded, separated_pair, terminated, tuple},
-    Finish, IResult,
 };
 use std::{ops::Range, str::FromStr};
 
+aoc_core::solution! {
+    type Input = (Range<i32>, Range<i32>);
+    type Part1 = i32;
+    type Part2 = usize;
+}
+
 pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(recognize(tuple((opt(tag("-")), digit1))), FromStr::from_str)(input)
 }
@@ -36,15 +40,8 @@ fn parse_rule(input: &str) -> IResult<&str, (Range<i32>, Range<i32>)> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> (Range<i32>, Range<i32>) {
-    match all_consuming(parse_rule)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<(Range<i32>, Range<i32>), ParseError> {
+    aoc_core::parse::all(input, parse_rule)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -86,7 +83,7 @@ fn simulate(vec: Vector, bounds: &(Range<i32>, Range<i32>)) -> Option<i32> {
         })
         .take_while(|&y| y >= bounds.1.start)
         .any(|y| bounds.1.contains(&y))
-        .then(|| max_y)
+        .then_some(max_y)
 }
 
 pub fn part1(target: &(Range<i32>, Range<i32>)) -> i32 {
@@ -107,3 +104,30 @@ pub fn part2(target: &(Range<i32>, Range<i32>)) -> usize {
         .flat_map(|vec| simulate(vec, target))
         .count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn target() -> impl Strategy<Value = (Range<i32>, Range<i32>)> {
+        let range = (-1000..1000, 1..100).prop_map(|(start, len)| start..start + len);
+        (range.clone(), range)
+    }
+
+    fn render((x, y): &(Range<i32>, Range<i32>)) -> String {
+        format!(
+            "target area: x={}..{}, y={}..{}\n",
+            x.start,
+            x.end - 1,
+            y.start,
+            y.end - 1
+        )
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(target, render, parse_input);
+    }
+}
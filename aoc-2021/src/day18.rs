@@ -1,15 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{delimited, separated_pair, terminated},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<SnailNumber>;
+    type Part1 = u64;
+    type Part2 = u64;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(digit1, FromStr::from_str)(input)
 }
@@ -43,15 +51,32 @@ fn all_numbers(input: &str) -> IResult<&str, Vec<SnailNumber>> {
     separated_list1(tag("\n"), map(snail_number, |sn| SnailNumber::parse(&sn)))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<SnailNumber> {
-    match all_consuming(terminated(all_numbers, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
+pub fn parse_input(input: &str) -> Result<Vec<SnailNumber>, ParseError> {
+    aoc_core::parse::all(input, terminated(all_numbers, tag("\n")))
+}
+
+fn generate_number(rng: &mut Rng, depth: usize, out: &mut String) {
+    out.push('[');
+    for side in 0..2 {
+        if side == 1 {
+            out.push(',');
+        }
+        if depth < 4 && rng.chance(0.6) {
+            generate_number(rng, depth + 1, out);
+        } else {
+            out.push(char::from(b'0' + rng.range(0..10)));
+        }
     }
-    .unwrap()
+    out.push(']');
+}
+
+/// `size` snailfish numbers, already reduced.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let mut number = String::new();
+        generate_number(rng, 1, &mut number);
+        number
+    })
 }
 
 #[derive(Debug, Default, Clone)]
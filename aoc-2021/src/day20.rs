@@ -1,33 +1,59 @@
-const WIDTH: usize = 100;
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = (Vec<u8>, Grid<u8>);
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 const GROW: usize = 1;
 
-pub fn parse_input(input: &str) -> (Vec<u8>, Vec<u8>) {
-    let mut lines = input.lines();
-
-    let algorithm: Vec<_> = lines
-        .next()
-        .unwrap()
-        .bytes()
-        .map(|c| match c {
-            b'.' => 0,
-            b'#' => 1,
-            _ => unreachable!(),
-        })
-        .collect();
+pub fn parse_input(input: &str) -> Result<(Vec<u8>, Grid<u8>), ParseError> {
+    let pixel = |c| match c {
+        b'.' => Some(0),
+        b'#' => Some(1),
+        _ => None,
+    };
+
+    // The enhancement algorithm is the first line, and the image follows
+    // after a blank one.
+    let (algorithm, image) = input
+        .split_once('\n')
+        .and_then(|(algorithm, rest)| Some((algorithm, rest.strip_prefix('\n')?)))
+        .ok_or_else(|| {
+            ParseError::new(
+                input,
+                input.lines().nth(1).unwrap_or(&input[input.len()..]),
+                "a blank line",
+            )
+        })?;
+
+    let algorithm = aoc_core::parse::cells(algorithm, "'.' or '#'", pixel)?;
+    let image = aoc_core::parse::grid(image, "'.' or '#'", pixel).map_err(|mut err| {
+        // Count lines from the top of the input, not of the image.
+        err.line += 2;
+        err
+    })?;
+
+    Ok((algorithm, image))
+}
 
-    lines.next().unwrap();
+/// An enhancement algorithm that flips the infinite background every
+/// step, like the puzzle's, and a `size` by `size` image.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
 
-    let image: Vec<_> = lines
-        .flat_map(|line| {
-            line.bytes().map(|c| match c {
-                b'.' => 0,
-                b'#' => 1,
-                _ => unreachable!(),
-            })
-        })
-        .collect();
+    let mut algorithm: Vec<char> = (0..512).map(|_| pixel(rng)).collect();
+    algorithm[0] = '#';
+    algorithm[511] = '.';
 
-    (algorithm, image)
+    let algorithm: String = algorithm.into_iter().collect();
+    algorithm + "\n\n" + &aoc_core::generate::grid(rng, size, size, pixel)
 }
 
 fn index(image: &[u8], pos: Option<usize>, default: u8) -> u16 {
@@ -52,13 +78,13 @@ fn full_window(point: usize, image: &[u8], width: usize, default: u8) -> u16 {
             .checked_sub(1)
             .filter(|p| p % width != width - 1)
             .and_then(|p| p.checked_add(width))
-            .filter(|&p| p < width * width),
-        point.checked_add(width).filter(|&p| p < width * width),
+            .filter(|&p| p < image.len()),
+        point.checked_add(width).filter(|&p| p < image.len()),
         point
             .checked_add(1)
             .filter(|p| p % width != 0)
             .and_then(|p| p.checked_add(width))
-            .filter(|&p| p < width * width),
+            .filter(|&p| p < image.len()),
     ];
 
     window
@@ -68,7 +94,7 @@ fn full_window(point: usize, image: &[u8], width: usize, default: u8) -> u16 {
 }
 
 fn window(point: usize, prev: u16, image: &[u8], width: usize, default: u8) -> u16 {
-    if point % width == 0 {
+    if point.is_multiple_of(width) {
         full_window(point, image, width, default)
     } else {
         let newdata = [
@@ -81,7 +107,7 @@ fn window(point: usize, prev: u16, image: &[u8], width: usize, default: u8) -> u
                 .checked_add(1)
                 .filter(|p| p % width != 0)
                 .and_then(|p| p.checked_add(width))
-                .filter(|&p| p < width * width),
+                .filter(|&p| p < image.len()),
         ];
 
         (prev & 0b111000000) << 1 & 0b111000000
@@ -94,7 +120,11 @@ fn window(point: usize, prev: u16, image: &[u8], width: usize, default: u8) -> u
 }
 
 fn step(image: &[u8], width: usize, algorithm: &[u8], generation: usize) -> (Vec<u8>, usize) {
-    let default = if generation % 2 != 0 { algorithm[0] } else { 0 };
+    let default = if !generation.is_multiple_of(2) {
+        algorithm[0]
+    } else {
+        0
+    };
 
     let height = image.len() / width;
     let new_width = width + GROW * 2;
@@ -117,9 +147,9 @@ fn step(image: &[u8], width: usize, algorithm: &[u8], generation: usize) -> (Vec
     (new_image, new_width)
 }
 
-fn solve<const N: usize>(algorithm: &[u8], image: &[u8]) -> usize {
-    let width = WIDTH;
-    let image = image.to_vec();
+fn solve<const N: usize>(algorithm: &[u8], image: &Grid<u8>) -> usize {
+    let width = image.width();
+    let image = image.cells().to_vec();
 
     (0..N)
         .fold((image, width), |(image, width), generation| {
@@ -131,10 +161,10 @@ fn solve<const N: usize>(algorithm: &[u8], image: &[u8]) -> usize {
         .count()
 }
 
-pub fn part1((algorithm, image): &(Vec<u8>, Vec<u8>)) -> usize {
+pub fn part1((algorithm, image): &(Vec<u8>, Grid<u8>)) -> usize {
     solve::<2>(algorithm, image)
 }
 
-pub fn part2((algorithm, image): &(Vec<u8>, Vec<u8>)) -> usize {
+pub fn part2((algorithm, image): &(Vec<u8>, Grid<u8>)) -> usize {
     solve::<50>(algorithm, image)
 }
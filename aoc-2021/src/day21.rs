@@ -1,13 +1,17 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map_res, opt, recognize},
-    error::Error,
+    combinator::{map_res, opt, recognize},
     sequence::{preceded, separated_pair, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = (u16, u16);
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(recognize(tuple((opt(tag("-")), digit1))), FromStr::from_str)(input)
 }
@@ -23,15 +27,8 @@ fn parse_state(input: &str) -> IResult<&str, (u16, u16)> {
     separated_pair(parse_rule, tag("\n"), parse_rule)(input)
 }
 
-pub fn parse_input(input: &str) -> (u16, u16) {
-    match all_consuming(terminated(parse_state, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<(u16, u16), ParseError> {
+    aoc_core::parse::all(input, terminated(parse_state, tag("\n")))
 }
 
 #[derive(Debug)]
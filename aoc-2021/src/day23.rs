@@ -1,5 +1,13 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Amphipod {
+use aoc_core::{parse::ParseError, pathfinding};
+
+aoc_core::solution! {
+    type Input = Rooms;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amphipod {
     Amber,
     Bronze,
     Copper,
@@ -15,298 +23,212 @@ impl Amphipod {
             Amphipod::Desert => 1000,
         }
     }
-}
-
-pub fn parse_input(_input: &str) {
-    // match all_consuming(terminated(parse_ops, tag("\n")))(input).finish() {
-    //     Ok((_, output)) => Ok(output),
-    //     Err(Error { input, code }) => Err(Error {
-    //         input: input.to_string(),
-    //         code,
-    //     }),
-    // }
-    // .unwrap();
-}
-
-const fn entry_energy(n: usize) -> usize {
-    let base = n * (n + 1) / 2;
-    base + base * 10 + base * 100 + base * 1000
-}
-
-fn exit_energy<const N: usize>(rooms: &[&[Amphipod; N]; 4]) -> usize {
-    let exit_energy: usize = rooms
-        .iter()
-        .flat_map(|room| {
-            room.iter()
-                .enumerate()
-                .map(|(idx, pod)| (idx + 1) * pod.energy())
-        })
-        .sum();
-    exit_energy
-}
 
-#[derive(Debug, Default, Clone, Copy)]
-struct BitBoard {
-    amber: u8,
-    bronze: u8,
-    copper: u8,
-    desert: u8,
-}
-
-impl BitBoard {
-    fn flatten(&self) -> u8 {
-        self.amber | self.bronze | self.copper | self.desert
+    /// The side room the amphipod belongs in.
+    const fn room(&self) -> usize {
+        match self {
+            Amphipod::Amber => 0,
+            Amphipod::Bronze => 1,
+            Amphipod::Copper => 2,
+            Amphipod::Desert => 3,
+        }
     }
 
-    fn is_empty(&self) -> bool {
-        self.flatten() == 0
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'A' => Some(Amphipod::Amber),
+            b'B' => Some(Amphipod::Bronze),
+            b'C' => Some(Amphipod::Copper),
+            b'D' => Some(Amphipod::Desert),
+            _ => None,
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Room<const N: usize> {
-    Room {
-        slots: [Option<Amphipod>; N],
-        pos: usize,
-    },
-    Empty,
-}
-
-impl<const N: usize> Room<N> {
-    fn extract(&self) -> Option<&Amphipod> {
-        match self {
-            Room::Room {
-                slots: room, pos, ..
-            } => room[*pos].as_ref(),
-            Room::Empty => None,
+/// The amphipods in each of the four side rooms, from the hallway down.
+pub type Rooms = [[Amphipod; 2]; 4];
+
+/// The rows part 2 unfolds between the first and last row of the rooms.
+const FOLDED: [[Amphipod; 4]; 2] = {
+    use Amphipod::*;
+    [
+        [Desert, Copper, Bronze, Amber],
+        [Desert, Bronze, Amber, Copper],
+    ]
+};
+
+pub fn parse_input(input: &str) -> Result<Rooms, ParseError> {
+    let mut lines = input.lines().skip(2);
+    let mut rooms = [[Amphipod::Amber; 2]; 4];
+
+    for depth in 0..2 {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::new(input, &input[input.len()..], "side room row"))?;
+        for (room, column) in rooms.iter_mut().zip([3, 5, 7, 9]) {
+            let at = line.get(column..).unwrap_or(&line[line.len()..]);
+            room[depth] = at
+                .bytes()
+                .next()
+                .and_then(Amphipod::from_byte)
+                .ok_or_else(|| ParseError::new(input, at, "amphipod"))?;
         }
     }
 
-    fn take(&mut self) {
-        match self {
-            Room::Room { slots: room, pos } => {
-                room[*pos].take();
-                *pos += 1;
-                if *pos == N {
-                    *self = Room::Empty;
-                }
-            }
-            Room::Empty => {}
-        }
-    }
+    Ok(rooms)
+}
 
-    fn is_empty(&self) -> bool {
-        self.extract().is_none()
-    }
+const HALLWAY: usize = 11;
+
+/// Where each side room opens onto the hallway. Amphipods never stop
+/// there.
+const fn door(room: usize) -> usize {
+    2 + room * 2
 }
 
-#[derive(Debug, Clone, Copy)]
+/// The amphipods in the hallway, and in side rooms `N` deep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Burrow<const N: usize> {
-    rooms: [Room<N>; 4],
-    hallway: BitBoard,
+    hallway: [Option<Amphipod>; HALLWAY],
+    rooms: [[Option<Amphipod>; N]; 4],
 }
 
 impl<const N: usize> Burrow<N> {
-    fn new(rooms: &[&[Amphipod; N]; 4]) -> Self {
-        let mut r: Vec<Room<N>> = Vec::new();
-
-        for &members in rooms {
-            let pods: Vec<Option<_>> = members.iter().copied().map(Some).collect();
-
-            r.push(Room::Room {
-                slots: pods.try_into().unwrap(),
-                pos: 0,
-            });
-        }
-
+    fn new(rooms: [[Amphipod; N]; 4]) -> Self {
         Self {
-            rooms: r.try_into().unwrap(),
-            hallway: BitBoard::default(),
+            hallway: [None; HALLWAY],
+            rooms: rooms.map(|room| room.map(Some)),
         }
     }
 
-    fn has_path(&self, start: usize, dist: usize) -> bool {
-        if dist == 0 {
-            true
-        } else {
-            let mask = ((2 << (dist - 1)) - 1) << (5 - dist - start);
-            self.hallway.flatten() & mask == 0
-        }
+    fn is_sorted(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(idx, room)| {
+            room.iter()
+                .all(|pod| pod.is_some_and(|pod| pod.room() == idx))
+        })
     }
 
-    fn commit(&mut self, pod: Amphipod, target: usize, mask: u8) {
-        match pod {
-            Amphipod::Amber => {
-                self.rooms[target].take();
-                self.hallway.amber |= mask;
-            }
-            Amphipod::Bronze => {
-                self.rooms[target].take();
-                self.hallway.bronze |= mask;
-            }
-            Amphipod::Copper => {
-                self.rooms[target].take();
-                self.hallway.copper |= mask;
-            }
-            Amphipod::Desert => {
-                self.rooms[target].take();
-                self.hallway.desert |= mask;
-            }
-        }
-
-        if self.rooms[0].is_empty() {
-            self.hallway.amber = 0;
-        }
-        if self.rooms[1].is_empty() {
-            self.hallway.bronze = 0;
-        }
-        if self.rooms[2].is_empty() {
-            self.hallway.copper = 0;
-        }
-        if self.rooms[3].is_empty() {
-            self.hallway.desert = 0;
-        }
+    /// Whether the room only holds amphipods that belong there, so
+    /// others can move in and none has to move out.
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .flatten()
+            .all(|pod| pod.room() == room)
     }
 
-    fn moves(&self, pos: usize) -> Option<(Amphipod, usize, Vec<(u8, usize)>)> {
-        let pod = self.rooms[pos].extract()?;
-
-        let energy = pod.energy();
-        let target = match pod {
-            Amphipod::Amber => 0,
-            Amphipod::Bronze => 1,
-            Amphipod::Copper => 2,
-            Amphipod::Desert => 3,
-        };
-
-        const COSTS: [usize; 7] = [2, 2, 4, 4, 4, 2, 2];
-        let hallway = self.hallway.flatten();
-        let mut moves = Vec::with_capacity(7);
-
-        let (start, end) = if pos < target {
-            (pos, target)
+    /// Whether the hallway is free from `from` to `to`, leaving out
+    /// `from` itself.
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let between = if from < to {
+            from + 1..to + 1
         } else {
-            (target, pos)
+            to..from
         };
+        self.hallway[between].iter().all(Option::is_none)
+    }
 
-        let dist = end - start;
-        let basecost = dist * 2;
-        let has_path = self.has_path(start, dist);
-
-        if has_path && self.rooms[target].is_empty() {
-            moves.push((0, basecost * energy));
-        } else {
-            if has_path || pos < target {
-                moves.extend(
-                    (0..start + 2)
-                        .rev()
-                        .map(|offset| (1 << (6 - offset), COSTS[offset]))
-                        .scan(0, |acc, (mask2, weight)| {
-                            let cost = if *acc == 0 {
-                                *acc += weight;
-                                2
-                            } else {
-                                *acc += weight;
-                                *acc
-                            };
-
-                            (mask2 & hallway == 0).then(|| (mask2, (basecost + cost) * energy))
-                        }),
-                );
-            }
-
-            if has_path || target < pos {
-                moves.extend(
-                    (end + 2..7)
-                        .map(|offset| (1 << (6 - offset), COSTS[offset]))
-                        .scan(0, |acc, (mask2, weight)| {
-                            let cost = if *acc == 0 {
-                                *acc += weight;
-                                2
-                            } else {
-                                *acc += weight;
-                                *acc
-                            };
-
-                            (mask2 & hallway == 0).then(|| (mask2, (basecost + cost) * energy))
-                        }),
-                );
+    /// A lower bound on the energy left to spend: every amphipod that
+    /// still has to move walks straight home, stepping aside by one if it
+    /// has to leave its own room.
+    fn estimate(&self) -> usize {
+        let hallway: usize = self
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(spot, pod)| {
+                let pod = (*pod)?;
+                Some((spot.abs_diff(door(pod.room())) + 1) * pod.energy())
+            })
+            .sum();
+
+        let mut rooms = 0;
+        for (room, slots) in self.rooms.iter().enumerate() {
+            // From the bottom up, amphipods at home stay put until one
+            // below them has to get out.
+            let mut blocked = false;
+            for (depth, pod) in slots.iter().enumerate().rev() {
+                let Some(pod) = pod else { continue };
+                blocked |= pod.room() != room;
+                if blocked {
+                    let walk = door(room).abs_diff(door(pod.room())).max(2);
+                    rooms += (depth + 1 + walk + 1) * pod.energy();
+                }
             }
         }
 
-        Some((*pod, pos, moves))
+        hallway + rooms
     }
-}
 
-fn solve<const N: usize>(rooms: &[&[Amphipod; N]; 4]) -> usize {
-    let mut stack = vec![(Burrow::<N>::new(rooms), 0)];
-    let mut min = usize::MAX;
+    /// Every burrow one amphipod's move away, and what the move costs.
+    /// An amphipod that can get home always might as well go right away,
+    /// so when one can, that's the only move.
+    fn moves(&self) -> Vec<(Self, usize)> {
+        // Into its room, once nothing else is left in there.
+        for (spot, pod) in self.hallway.iter().enumerate() {
+            let Some(pod) = *pod else { continue };
+            let room = pod.room();
+            if !self.is_settled(room) || !self.is_clear(spot, door(room)) {
+                continue;
+            }
+            let depth = self.rooms[room].iter().rposition(Option::is_none).unwrap();
 
-    while let Some((burrow, cost)) = stack.pop() {
-        for t in (0..4).rev() {
-            if let Some((pod, target, futures)) = burrow.moves(t) {
-                for future in futures {
-                    let mut burrow = burrow;
-                    let cost = cost + future.1;
-                    if cost < min {
-                        burrow.commit(pod, target, future.0);
+            let mut next = *self;
+            next.hallway[spot] = None;
+            next.rooms[room][depth] = Some(pod);
+            return vec![(next, (spot.abs_diff(door(room)) + depth + 1) * pod.energy())];
+        }
+
+        // Out of a room holding someone that doesn't belong, to any spot
+        // in the hallway it can reach.
+        let mut moves = Vec::new();
+        for room in 0..4 {
+            if self.is_settled(room) {
+                continue;
+            }
+            let depth = self.rooms[room].iter().position(Option::is_some).unwrap();
+            let pod = self.rooms[room][depth].unwrap();
 
-                        if burrow.hallway.is_empty() {
-                            min = cost;
-                        } else {
-                            stack.push((burrow, cost));
-                        }
-                    }
+            for spot in (0..HALLWAY).filter(|&spot| !(2..=8).contains(&spot) || spot % 2 == 1) {
+                if !self.is_clear(door(room), spot) {
+                    continue;
                 }
+
+                let mut next = *self;
+                next.rooms[room][depth] = None;
+                next.hallway[spot] = Some(pod);
+                moves.push((next, (depth + 1 + door(room).abs_diff(spot)) * pod.energy()));
             }
         }
-    }
 
-    min
+        moves
+    }
 }
 
-pub fn part1(input: &()) -> usize {
-    let rooms: [&[Amphipod; 2]; 4] = [
-        &[Amphipod::Amber, Amphipod::Desert],
-        &[Amphipod::Copper, Amphipod::Desert],
-        &[Amphipod::Bronze, Amphipod::Bronze],
-        &[Amphipod::Amber, Amphipod::Copper],
-    ];
-
-    let energy = entry_energy(2) + exit_energy(&rooms) + solve(&rooms);
-    assert_eq!(energy, 18195);
+fn solve<const N: usize>(rooms: [[Amphipod; N]; 4]) -> usize {
+    let graph = pathfinding::from_fn(|burrow: Burrow<N>| burrow.moves());
+    let (_, energy) = pathfinding::astar(
+        &graph,
+        [Burrow::new(rooms)],
+        |burrow| burrow.is_sorted(),
+        |burrow| burrow.estimate(),
+    )
+    .target()
+    .unwrap();
     energy
 }
 
-pub fn part2(input: &()) -> usize {
-    let rooms: [&[Amphipod; 4]; 4] = [
-        &[
-            Amphipod::Amber,
-            Amphipod::Desert,
-            Amphipod::Desert,
-            Amphipod::Desert,
-        ],
-        &[
-            Amphipod::Copper,
-            Amphipod::Copper,
-            Amphipod::Bronze,
-            Amphipod::Desert,
-        ],
-        &[
-            Amphipod::Bronze,
-            Amphipod::Bronze,
-            Amphipod::Amber,
-            Amphipod::Bronze,
-        ],
-        &[
-            Amphipod::Amber,
-            Amphipod::Amber,
-            Amphipod::Copper,
-            Amphipod::Copper,
-        ],
-    ];
+pub fn part1(input: &Rooms) -> usize {
+    solve(*input)
+}
 
-    let energy = entry_energy(4) + exit_energy(&rooms) + solve(&rooms);
-    assert_eq!(energy, 50265);
-    energy
+pub fn part2(input: &Rooms) -> usize {
+    solve(std::array::from_fn(|room| {
+        [
+            input[room][0],
+            FOLDED[0][room],
+            FOLDED[1][room],
+            input[room][1],
+        ]
+    }))
 }
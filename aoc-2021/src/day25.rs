@@ -1,7 +1,11 @@
-use std::fmt::Write;
+use aoc_core::{grid::Grid, parse::ParseError};
+use std::fmt;
 
-const WIDTH: usize = 139;
-const HEIGHT: usize = 137;
+aoc_core::solution! {
+    type Input = Grid<Cucumber>;
+    type Part1 = usize;
+    type Part2 = &'static str;
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -17,35 +21,43 @@ impl Cucumber {
     }
 }
 
-fn below(point: usize) -> usize {
+impl fmt::Display for Cucumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Cucumber::Right => '>',
+            Cucumber::Down => 'v',
+            Cucumber::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// The spot below `point`, wrapping around to the top row.
+fn below(point: usize, width: usize, len: usize) -> usize {
     point
-        .checked_add(WIDTH)
-        .filter(|&p| p < WIDTH * HEIGHT)
-        .unwrap_or(point % WIDTH)
+        .checked_add(width)
+        .filter(|&p| p < len)
+        .unwrap_or(point % width)
 }
 
-fn right(point: usize) -> usize {
+/// The spot right of `point`, wrapping around to the start of the row.
+fn right(point: usize, width: usize) -> usize {
     point
         .checked_add(1)
-        .filter(|p| p % WIDTH != 0)
-        .unwrap_or(point / WIDTH * WIDTH)
+        .filter(|p| p % width != 0)
+        .unwrap_or(point / width * width)
 }
 
-pub fn parse_input(input: &str) -> Vec<Cucumber> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.bytes().map(|byte| match byte {
-                b'>' => Cucumber::Right,
-                b'v' => Cucumber::Down,
-                b'.' => Cucumber::Empty,
-                _ => unreachable!(),
-            })
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Grid<Cucumber>, ParseError> {
+    aoc_core::parse::grid(input, "'>', 'v' or '.'", |byte| match byte {
+        b'>' => Some(Cucumber::Right),
+        b'v' => Some(Cucumber::Down),
+        b'.' => Some(Cucumber::Empty),
+        _ => None,
+    })
 }
 
-fn step(board: &[Cucumber]) -> (Vec<Cucumber>, usize) {
+fn step(board: &[Cucumber], width: usize) -> (Vec<Cucumber>, usize) {
     let mut newboard = vec![Cucumber::Empty; board.len()];
     let mut downs = Vec::with_capacity(4800);
     let mut moves = 0;
@@ -53,7 +65,7 @@ fn step(board: &[Cucumber]) -> (Vec<Cucumber>, usize) {
     for (pos, cell) in board.iter().enumerate() {
         match cell {
             Cucumber::Right => {
-                let target = right(pos);
+                let target = right(pos, width);
                 if board[target].is_empty() {
                     moves += 1;
                     newboard[target] = Cucumber::Right;
@@ -69,7 +81,7 @@ fn step(board: &[Cucumber]) -> (Vec<Cucumber>, usize) {
     }
 
     for pos in downs {
-        let target = below(pos);
+        let target = below(pos, width, board.len());
         let promote = match board[target] {
             Cucumber::Down => false,
             _ => newboard[target].is_empty(),
@@ -86,36 +98,21 @@ fn step(board: &[Cucumber]) -> (Vec<Cucumber>, usize) {
     (newboard, moves)
 }
 
-fn dump(board: &[Cucumber]) -> String {
-    let mut output = String::with_capacity(board.len());
-
-    for (idx, p) in board.iter().enumerate() {
-        let c = match p {
-            Cucumber::Right => '>',
-            Cucumber::Down => 'v',
-            Cucumber::Empty => '.',
-        };
-        write!(&mut output, "{}", c).unwrap();
-
-        if idx % WIDTH == WIDTH - 1 {
-            writeln!(&mut output).unwrap();
-        }
-    }
-    output
-}
-
-pub fn part1(input: &[Cucumber]) -> usize {
-    let board = input.to_vec();
+pub fn part1(input: &Grid<Cucumber>) -> usize {
+    let board = input.cells().to_vec();
     (1..)
         .scan(board, |board, idx| {
-            let (newboard, count) = step(board);
+            let (newboard, count) = step(board, input.width());
             *board = newboard;
-            (count > 0).then(|| idx)
+            (count > 0).then_some(idx)
         })
         .last()
-        .unwrap() + 1
+        .unwrap()
+        + 1
 }
 
-pub fn part2(input: &[Cucumber]) -> usize {
-    0
+/// The last day has no second puzzle: its star is for having all the
+/// others.
+pub fn part2(_: &Grid<Cucumber>) -> &'static str {
+    "⭐"
 }
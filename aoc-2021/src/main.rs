@@ -1,7 +1,6 @@
-use std::{
-    fs,
-    time::{Duration, Instant},
-};
+use aoc_core::{days, Year};
+
+aoc_core::counting_allocator!();
 
 mod day01;
 mod day02;
@@ -23,51 +22,32 @@ mod day17;
 mod day18;
 mod day20;
 mod day21;
+mod day23;
+mod day25;
 
-fn time<F, R>(f: F) -> (Duration, R)
-where
-    F: Fn() -> R,
-{
-    let now = Instant::now();
-    let result = f();
-    (now.elapsed(), result)
-}
-
-macro_rules! day {
-    ($mod:ident) => {
-        let input = fs::read_to_string(concat!("./data/", stringify!($mod), ".txt")).unwrap();
-        let (dur, input) = time(|| $mod::parse_input(&input));
-        println!(":: {}", stringify!($mod));
-        println!("generator: {:?}", dur);
-        println!("part1: {:?}", time(|| $mod::part1(&input)));
-        println!("part2: {:?}", time(|| $mod::part2(&input)));
-        println!();
-    };
+fn year() -> Year {
+    Year {
+        year: 2021,
+        days: days![
+            day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+            day13, day14, day15, day16, day17, day18, day20, day21, day23, day25,
+        ],
+    }
 }
 
 fn main() {
-    let now = Instant::now();
+    aoc_core::run(year());
+}
 
-    day!(day01);
-    day!(day02);
-    day!(day03);
-    day!(day04);
-    day!(day05);
-    day!(day06);
-    day!(day07);
-    day!(day08);
-    day!(day09);
-    day!(day10);
-    day!(day11);
-    day!(day12);
-    day!(day13);
-    day!(day14);
-    day!(day15);
-    day!(day16);
-    day!(day17);
-    day!(day18);
-    day!(day20);
-    day!(day21);
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
 
-    println!("total: {:?}", now.elapsed());
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generated_inputs_parse() {
+        aoc_core::generate::check(&super::year());
+    }
 }
@@ -1,14 +1,22 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::terminated,
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 250 };
+}
+
 fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(digit1, FromStr::from_str)(input)
 }
@@ -21,15 +29,19 @@ fn parse_groups(input: &str) -> IResult<&str, Vec<u32>> {
     separated_list1(tag("\n\n"), parse_group)(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<u32> {
-    match all_consuming(terminated(parse_groups, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_groups, tag("\n")))
+}
+
+/// The food carried by `size` elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size)
+        .map(|_| {
+            let items = rng.range(1..16);
+            aoc_core::generate::lines(rng, items, |rng| rng.range(1000..70000u32).to_string())
+        })
+        .collect();
+    elves.join("\n")
 }
 
 fn iter_packs(packs: &[u32]) -> impl Iterator<Item = u32> + '_ {
@@ -1,13 +1,21 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::one_of,
-    combinator::{all_consuming, map},
-    error::Error,
+    combinator::map,
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
 
+aoc_core::solution! {
+    type Input = Vec<(Play, Cipher)>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 2500 };
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Play {
     Rock = 1,
@@ -65,15 +73,19 @@ fn parse_lines(input: &str) -> IResult<&str, Vec<(Play, Cipher)>> {
     separated_list1(tag("\n"), parse_line)(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<(Play, Cipher)> {
-    match all_consuming(terminated(parse_lines, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<(Play, Cipher)>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_lines, tag("\n")))
+}
+
+/// A strategy guide for `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        format!(
+            "{} {}",
+            rng.pick(&['A', 'B', 'C']),
+            rng.pick(&['X', 'Y', 'Z'])
+        )
+    })
 }
 
 pub fn part1(input: &[(Play, Cipher)]) -> u32 {
@@ -1,3 +1,15 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<(u64, u64)>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 fn item_priority(item: u8) -> u8 {
     match item {
         b'a'..=b'z' => item - b'a' + 1,
@@ -12,17 +24,53 @@ fn parse_compartment(items: &[u8]) -> u64 {
         .fold(0u64, |acc, &item| acc | 1u64 << item_priority(item))
 }
 
-pub fn parse_input(input: &str) -> Vec<(u64, u64)> {
+pub fn parse_input(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
     input
         .lines()
         .map(|line| {
+            if let Some(idx) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::new(input, &line[idx..], "item"));
+            }
+
             let bytes = line.as_bytes();
             let (left, right) = bytes.split_at(bytes.len() / 2);
-            (parse_compartment(left), parse_compartment(right))
+            Ok((parse_compartment(left), parse_compartment(right)))
         })
         .collect()
 }
 
+/// Rucksacks for `size` groups of three elves. Each group only has its
+/// badge in common, and each rucksack a single item in both compartments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+
+    let mut out = String::new();
+    for _ in 0..size {
+        rng.shuffle(&mut items);
+        let (badge, others) = items.split_last().unwrap();
+
+        // Every elf of the group packs from their own share of the items,
+        // so that the badge is the only one they have in common.
+        for share in others.chunks(others.len() / 3).take(3) {
+            let (shared, share) = share.split_first().unwrap();
+            let (left, right) = share.split_at(share.len() / 2);
+            let count = rng.range(4..left.len().min(right.len()));
+
+            let mut first = vec![*shared, *badge];
+            first.extend(&left[..count]);
+            let mut second = vec![*shared];
+            second.extend(&right[..count + 1]);
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            out.push_str(std::str::from_utf8(&first).unwrap());
+            out.push_str(std::str::from_utf8(&second).unwrap());
+            out.push('\n');
+        }
+    }
+    out
+}
+
 pub fn part1(input: &[(u64, u64)]) -> u32 {
     input
         .iter()
@@ -1,14 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<(Interval, Interval)>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Interval(u8, u8);
 
 impl Interval {
@@ -43,15 +52,17 @@ fn parse_lines(input: &str) -> IResult<&str, Vec<(Interval, Interval)>> {
     separated_list1(tag("\n"), parse_line)(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<(Interval, Interval)> {
-    match all_consuming(terminated(parse_lines, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<(Interval, Interval)>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_lines, tag("\n")))
+}
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let (a, b) = (rng.range(1..100u32), rng.range(1..100u32));
+        format!("{}-{}", a.min(b), a.max(b))
+    };
+    aoc_core::generate::lines(rng, size, |rng| format!("{},{}", range(rng), range(rng)))
 }
 
 pub fn part1(input: &[(Interval, Interval)]) -> usize {
@@ -64,3 +75,28 @@ pub fn part1(input: &[(Interval, Interval)]) -> usize {
 pub fn part2(input: &[(Interval, Interval)]) -> usize {
     input.iter().filter(|(x, y)| x.overlaps(y)).count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn assignments() -> impl Strategy<Value = Vec<(Interval, Interval)>> {
+        let interval =
+            (1..100u8, 0..10u8).prop_map(|(start, len)| Interval::new(start, start + len));
+        prop::collection::vec((interval.clone(), interval), 1..30)
+    }
+
+    fn render(assignments: &[(Interval, Interval)]) -> String {
+        assignments
+            .iter()
+            .map(|(a, b)| format!("{}-{},{}-{}\n", a.0, a.1, b.0, b.1))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(assignments, |assignments| render(assignments), parse_input);
+    }
+}
@@ -1,15 +1,24 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{digit1, satisfy},
-    combinator::{all_consuming, map, map_res, opt},
-    error::Error,
+    combinator::{map, map_res, opt, verify},
     multi::separated_list1,
     sequence::{delimited, preceded, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = (Vec<Vec<u8>>, Vec<Op>);
+    type Part1 = String;
+    type Part2 = String;
+    const GENERATOR = Generator { generate, size: 500 };
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Op {
     amount: usize,
     src: usize,
@@ -55,12 +64,16 @@ fn parse_stack_desc(input: &str) -> IResult<&str, Vec<Token>> {
     )(input)
 }
 
+fn label(input: &str) -> IResult<&str, usize> {
+    verify(number, |&label| label > 0)(input)
+}
+
 fn parse_operation(input: &str) -> IResult<&str, Op> {
     map(
         tuple((
             preceded(tag("move "), number),
-            preceded(tag(" from "), number),
-            preceded(tag(" to "), number),
+            preceded(tag(" from "), label),
+            preceded(tag(" to "), label),
         )),
         |(amount, src, dest): (usize, usize, usize)| Op::new(amount, src - 1, dest - 1),
     )(input)
@@ -80,21 +93,62 @@ fn build_stacks(lines: Vec<Vec<Token>>) -> Vec<Vec<u8>> {
 }
 
 fn parse_stacks_and_procedure(input: &str) -> IResult<&str, (Vec<Vec<u8>>, Vec<Op>)> {
-    tuple((
-        map(separated_list1(tag("\n"), parse_stack_desc), build_stacks),
-        preceded(tag("\n\n"), separated_list1(tag("\n"), parse_operation)),
-    ))(input)
-}
-
-pub fn parse_input(input: &str) -> (Vec<Vec<u8>>, Vec<Op>) {
-    match all_consuming(terminated(parse_stacks_and_procedure, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
+    let (input, stacks) = map(separated_list1(tag("\n"), parse_stack_desc), build_stacks)(input)?;
+
+    // Moves can only go between the stacks drawn above them.
+    let operation = verify(parse_operation, |op: &Op| {
+        op.src < stacks.len() && op.dest < stacks.len()
+    });
+    let (input, ops) = preceded(tag("\n\n"), separated_list1(tag("\n"), operation))(input)?;
+
+    Ok((input, (stacks, ops)))
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<Vec<u8>>, Vec<Op>), ParseError> {
+    aoc_core::parse::all(input, terminated(parse_stacks_and_procedure, tag("\n")))
+}
+
+/// Nine stacks of crates and `size` moves, which never take more crates
+/// than a stack has.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const STACKS: usize = 9;
+
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.range(1..9)).collect();
+    let top = *heights.iter().max().unwrap();
+
+    let mut out = String::new();
+    for level in (0..top).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", rng.range(b'A'..b'Z' + 1) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        out.push_str(&row.join(" "));
+        out.push('\n');
     }
-    .unwrap()
+    let labels: Vec<String> = (1..=STACKS).map(|label| format!(" {} ", label)).collect();
+    out.push_str(&labels.join(" "));
+    out.push_str("\n\n");
+
+    let moves = aoc_core::generate::lines(rng, size, |rng| {
+        let src = loop {
+            let src = rng.range(0..STACKS);
+            if heights[src] > 0 {
+                break src;
+            }
+        };
+        let dest = (src + rng.range(1..STACKS)) % STACKS;
+        let amount = rng.range(1..heights[src].min(10) + 1);
+        heights[src] -= amount;
+        heights[dest] += amount;
+        format!("move {} from {} to {}", amount, src + 1, dest + 1)
+    });
+    out + &moves
 }
 
 trait CrateMover {
@@ -176,3 +230,58 @@ pub fn part2((state, moves): &(Vec<Vec<u8>>, Vec<Op>)) -> String {
     simulate(&mut state, moves, CrateMover9001::new());
     top_of_stacks(&state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    type Drawing = (Vec<Vec<u8>>, Vec<Op>);
+
+    fn drawing() -> impl Strategy<Value = Drawing> {
+        prop::collection::vec(prop::collection::vec(b'A'..=b'Z', 0..6), 1..10).prop_flat_map(
+            |stacks| {
+                let len = stacks.len();
+                let op = (1..10usize, 0..len, 0..len)
+                    .prop_map(|(amount, src, dest)| Op::new(amount, src, dest));
+                (Just(stacks), prop::collection::vec(op, 1..10))
+            },
+        )
+    }
+
+    fn render((stacks, ops): &Drawing) -> String {
+        let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let mut out = String::new();
+        for level in (0..height).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(&c) => format!("[{}]", c as char),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            out += &row.join(" ");
+            out += "\n";
+        }
+        let labels: Vec<String> = (1..=stacks.len())
+            .map(|label| format!(" {} ", label))
+            .collect();
+        out += &labels.join(" ");
+        out += "\n\n";
+        for op in ops {
+            out += &format!(
+                "move {} from {} to {}\n",
+                op.amount,
+                op.src + 1,
+                op.dest + 1
+            );
+        }
+        out
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(drawing, render, parse_input);
+    }
+}
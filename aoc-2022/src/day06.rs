@@ -1,5 +1,29 @@
-pub fn parse_input(input: &str) -> &[u8] {
-    input.as_bytes()
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input<'a> = &'a [u8];
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 4096 };
+}
+
+pub fn parse_input(input: &str) -> Result<&[u8], ParseError> {
+    Ok(input.as_bytes())
+}
+
+/// A datastream of about `size` characters, ending in a start-of-message
+/// marker if there wasn't one earlier.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stream: Vec<u8> = (0..size).map(|_| rng.range(b'a'..b'z' + 1)).collect();
+
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    rng.shuffle(&mut marker);
+    stream.extend(&marker[..14]);
+
+    String::from_utf8(stream).unwrap() + "\n"
 }
 
 fn find_packet<const N: usize>(data: &[u8]) -> Option<usize> {
@@ -1,16 +1,21 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{digit1, satisfy},
-    combinator::{all_consuming, map, map_res, recognize},
-    error::Error,
+    combinator::{map, map_res, recognize},
     multi::{many1, separated_list1},
     sequence::{preceded, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum CdOpt<'a> {
     Root,
     Up,
@@ -24,6 +29,7 @@ pub enum Cmd<'a> {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum Output<'a> {
     Cmd(Cmd<'a>),
     File(u64),
@@ -38,7 +44,7 @@ fn parse_path(input: &str) -> IResult<&str, &str> {
     recognize(many1(satisfy(|c| c.is_ascii_graphic())))(input)
 }
 
-fn parse_output(input: &str) -> IResult<&str, Vec<Output>> {
+fn parse_output(input: &str) -> IResult<&str, Vec<Output<'_>>> {
     separated_list1(
         tag("\n"),
         alt((
@@ -56,16 +62,19 @@ fn parse_output(input: &str) -> IResult<&str, Vec<Output>> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<u64> {
-    match all_consuming(terminated(parse_output, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_output, tag("\n")))
+        .map(|ops| filesystem_from_replay(&ops))
+}
+
+/// Leave the current directory, like `cd ..` does, which stays put at
+/// the root.
+fn leave(stack: &mut Vec<u64>, folders: &mut Vec<u64>) {
+    if stack.len() > 1 {
+        let dir_size = stack.pop().unwrap();
+        folders.push(dir_size);
+        *stack.last_mut().unwrap() += dir_size;
     }
-    .map(|ops| filesystem_from_replay(&ops))
-    .unwrap()
 }
 
 fn filesystem_from_replay(input: &[Output]) -> Vec<u64> {
@@ -73,14 +82,14 @@ fn filesystem_from_replay(input: &[Output]) -> Vec<u64> {
     let mut stack = Vec::with_capacity(20);
 
     stack.push(0);
-    for op in &input[1..] {
+    for op in input {
         match op {
-            Output::Cmd(Cmd::Cd(CdOpt::Root)) => todo!(),
-            Output::Cmd(Cmd::Cd(CdOpt::Up)) => {
-                let dir_size = stack.pop().unwrap();
-                folders.push(dir_size);
-                *stack.last_mut().unwrap() += dir_size;
+            Output::Cmd(Cmd::Cd(CdOpt::Root)) => {
+                while stack.len() > 1 {
+                    leave(&mut stack, &mut folders);
+                }
             }
+            Output::Cmd(Cmd::Cd(CdOpt::Up)) => leave(&mut stack, &mut folders),
             Output::Cmd(Cmd::Cd(CdOpt::Chdir(_))) => {
                 stack.push(0);
             }
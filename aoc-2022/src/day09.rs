@@ -1,15 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
 use std::{collections::HashSet, str::FromStr};
 
+aoc_core::solution! {
+    type Input = Vec<Move>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 2000 };
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Left,
@@ -18,7 +26,7 @@ pub enum Direction {
     Down,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Move(Direction, usize);
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -87,20 +95,21 @@ fn parse_output(input: &str) -> IResult<&str, Vec<Move>> {
     separated_list1(tag("\n"), parse_move)(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Move> {
-    match all_consuming(terminated(parse_output, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_output, tag("\n")))
+}
+
+/// `size` motions of the head.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        format!("{} {}", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1..20))
+    })
 }
 
 fn simulate_rope<const LEN: usize>(input: &[Move]) -> usize {
     let mut rope = [Point::default(); LEN];
-    let mut visited = HashSet::new();
+    // The tail's starting position counts as visited too.
+    let mut visited = HashSet::from([rope[LEN - 1]]);
 
     for &Move(direction, steps) in input {
         'move_rope: for _ in 0..steps {
@@ -127,3 +136,45 @@ pub fn part1(input: &[Move]) -> usize {
 pub fn part2(input: &[Move]) -> usize {
     simulate_rope::<10>(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn motions() -> impl Strategy<Value = Vec<Move>> {
+        let direction = prop::sample::select(
+            &[
+                Direction::Left,
+                Direction::Right,
+                Direction::Up,
+                Direction::Down,
+            ][..],
+        );
+        prop::collection::vec(
+            (direction, 1..20usize).prop_map(|(direction, steps)| Move(direction, steps)),
+            1..30,
+        )
+    }
+
+    fn render(motions: &[Move]) -> String {
+        motions
+            .iter()
+            .map(|Move(direction, steps)| {
+                let direction = match direction {
+                    Direction::Left => 'L',
+                    Direction::Right => 'R',
+                    Direction::Up => 'U',
+                    Direction::Down => 'D',
+                };
+                format!("{} {}\n", direction, steps)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(motions, |motions| render(motions), parse_input);
+    }
+}
@@ -1,16 +1,23 @@
+use aoc_core::{
+    ocr,
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt, recognize},
-    error::Error,
+    combinator::{map, map_res, opt, recognize},
     multi::separated_list1,
     sequence::{preceded, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
-#[derive(Debug)]
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = i64;
+    type Part2 = Result<String, ocr::Error>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Op {
     Noop,
     Addx(i64),
@@ -30,15 +37,8 @@ fn parse_ops(input: &str) -> IResult<&str, Vec<Op>> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Op> {
-    match all_consuming(terminated(parse_ops, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_ops, tag("\n")))
 }
 
 struct Cpu<'a> {
@@ -88,9 +88,9 @@ pub fn part1(input: &[Op]) -> i64 {
     sum
 }
 
-pub fn part2(input: &[Op]) {
+pub fn part2(input: &[Op]) -> Result<String, ocr::Error> {
     let mut cpu = Cpu::new(input);
-    let mut screen = [b' '; 40 * 6];
+    let mut screen = [b'.'; 40 * 6];
 
     for cycle in 1..=240 {
         let pixel = cycle - 1;
@@ -100,7 +100,38 @@ pub fn part2(input: &[Op]) {
         }
     }
 
-    for row in screen.chunks(40) {
-        println!("{}", std::str::from_utf8(row).unwrap());
+    let screen = screen
+        .chunks(40)
+        .map(|row| std::str::from_utf8(row).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ocr::read_str(&screen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn program() -> impl Strategy<Value = Vec<Op>> {
+        let op = prop_oneof![Just(Op::Noop), (-40..40i64).prop_map(Op::Addx)];
+        prop::collection::vec(op, 1..30)
+    }
+
+    fn render(program: &[Op]) -> String {
+        program
+            .iter()
+            .map(|op| match op {
+                Op::Noop => "noop\n".to_string(),
+                Op::Addx(value) => format!("addx {}\n", value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(program, |program| render(program), parse_input);
     }
 }
@@ -1,15 +1,19 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt, recognize},
-    error::Error,
+    combinator::{map, map_res, opt, recognize},
     multi::separated_list1,
     sequence::{preceded, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<Monkey>;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 #[derive(Debug)]
 pub struct Monkey {
     starting_items: Vec<u64>,
@@ -80,15 +84,8 @@ fn parse_monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
     separated_list1(tag("\n\n"), parse_monkey)(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Monkey> {
-    match all_consuming(terminated(parse_monkeys, tag("\n")))(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    aoc_core::parse::all(input, terminated(parse_monkeys, tag("\n")))
 }
 
 impl Expr {
@@ -105,7 +102,7 @@ impl Expr {
 impl Monkey {
     fn inspect_and_throw<const WORRY: u64>(&self, item: u64) -> (usize, u64) {
         let item = self.operation.eval(item) / WORRY;
-        let dest = if item % self.test == 0 {
+        let dest = if item.is_multiple_of(self.test) {
             self.true_dest
         } else {
             self.false_dest
@@ -1,4 +1,6 @@
-use std::time::{Duration, Instant};
+use aoc_core::{days, Year};
+
+aoc_core::counting_allocator!();
 
 mod day01;
 mod day02;
@@ -11,40 +13,26 @@ mod day09;
 mod day10;
 mod day11;
 
-fn time<F, R>(f: F) -> (Duration, R)
-where
-    F: Fn() -> R,
-{
-    let now = Instant::now();
-    let result = f();
-    (now.elapsed(), result)
-}
-
-macro_rules! day {
-    ($mod:ident) => {
-        let input = include_str!(concat!("../data/", stringify!($mod), ".txt"));
-        let (dur, input) = time(|| $mod::parse_input(&input));
-        println!(":: {}", stringify!($mod));
-        println!("generator: {:?}", dur);
-        println!("part1: {:?}", time(|| $mod::part1(&input)));
-        println!("part2: {:?}", time(|| $mod::part2(&input)));
-        println!();
-    };
+fn year() -> Year {
+    Year {
+        year: 2022,
+        days: days![day01, day02, day03, day04, day05, day06, day07, day09, day10, day11,],
+    }
 }
 
 fn main() {
-    let now = Instant::now();
+    aoc_core::run(year());
+}
 
-    day!(day01);
-    day!(day02);
-    day!(day03);
-    day!(day04);
-    day!(day05);
-    day!(day06);
-    day!(day07);
-    day!(day09);
-    day!(day10);
-    day!(day11);
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
 
-    println!("total: {:?}", now.elapsed());
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generated_inputs_parse() {
+        aoc_core::generate::check(&super::year());
+    }
 }
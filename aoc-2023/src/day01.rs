@@ -1,3 +1,15 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Vec<Vec<Token>>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Token {
     Digit(u32),
@@ -46,16 +58,37 @@ fn parse_line(bytes: &[u8]) -> Vec<Token> {
         .collect()
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<Token>> {
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
     let mut buffer = Vec::with_capacity(1 << 7);
-    input
+    Ok(input
         .lines()
         .map(|line| {
             buffer.clear();
             buffer.extend(line.bytes());
             parse_line(&buffer)
         })
-        .collect()
+        .collect())
+}
+
+/// `size` lines of calibration document, each with at least one digit
+/// and a few spelled out ones.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    aoc_core::generate::lines(rng, size, |rng| {
+        let mut line = String::new();
+        let digit = rng.range(0..6);
+        for token in 0..rng.range(6..12) {
+            match rng.below(3) {
+                _ if token == digit => line.push(char::from(b'0' + rng.range(1..10))),
+                0 => line.push_str(rng.pick::<&str>(&WORDS)),
+                _ => line.push(rng.range(b'a'..b'z' + 1) as char),
+            }
+        }
+        line
+    })
 }
 
 pub fn part1(input: &[Vec<Token>]) -> u32 {
@@ -64,7 +97,7 @@ pub fn part1(input: &[Vec<Token>]) -> u32 {
         .map(|line| {
             let mut it = line.iter().filter_map(Token::digit);
             let first = it.next().unwrap_or_default();
-            first * 10 + it.last().unwrap_or(first)
+            first * 10 + it.next_back().unwrap_or(first)
         })
         .sum()
 }
@@ -75,7 +108,7 @@ pub fn part2(input: &[Vec<Token>]) -> u32 {
         .map(|line| {
             let mut it = line.iter().map(Token::value);
             let first = it.next().unwrap_or_default();
-            first * 10 + it.last().unwrap_or(first)
+            first * 10 + it.next_back().unwrap_or(first)
         })
         .sum()
 }
@@ -1,15 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res, opt},
-    error::Error,
+    combinator::{map, map_res, opt},
     multi::{fold_many0, separated_list1},
     sequence::{preceded, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<Vec<Set>>;
+    type Part1 = usize;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 #[derive(Debug)]
 enum Rule {
     Red(u32),
@@ -17,7 +25,7 @@ enum Rule {
     Blue(u32),
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Set {
     red: u32,
     green: u32,
@@ -81,15 +89,28 @@ fn parse_games(input: &str) -> IResult<&str, Vec<Vec<Set>>> {
     terminated(separated_list1(tag("\n"), parse_game), opt(tag("\n")))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<Set>> {
-    match all_consuming(parse_games)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Set>>, ParseError> {
+    aoc_core::parse::all(input, parse_games)
+}
+
+/// `size` games of a few draws each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut game = 0;
+    aoc_core::generate::lines(rng, size, |rng| {
+        game += 1;
+        let draws: Vec<String> = (0..rng.range(1..7))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                let cubes: Vec<String> = colors[..rng.range(1..4)]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1..20), color))
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        format!("Game {}: {}", game, draws.join("; "))
+    })
 }
 
 pub fn part1(input: &[Vec<Set>]) -> usize {
@@ -110,3 +131,35 @@ pub fn part2(input: &[Vec<Set>]) -> u32 {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn games() -> impl Strategy<Value = Vec<Vec<Set>>> {
+        let set =
+            (0..20u32, 0..20u32, 0..20u32).prop_map(|(red, green, blue)| Set { red, green, blue });
+        prop::collection::vec(prop::collection::vec(set, 1..6), 1..20)
+    }
+
+    fn render(games: &[Vec<Set>]) -> String {
+        games
+            .iter()
+            .enumerate()
+            .map(|(idx, sets)| {
+                let sets: Vec<String> = sets
+                    .iter()
+                    .map(|set| format!("{} red, {} green, {} blue", set.red, set.green, set.blue))
+                    .collect();
+                format!("Game {}: {}\n", idx + 1, sets.join("; "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(games, |games| render(games), parse_input);
+    }
+}
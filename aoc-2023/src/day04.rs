@@ -1,15 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::{digit1, space1},
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res, verify},
     multi::{fold_many0, separated_list1},
     sequence::{preceded, separated_pair, terminated, tuple},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
-#[derive(Debug)]
+aoc_core::solution! {
+    type Input = Vec<Card>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const GENERATOR = Generator { generate, size: 187 };
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Card {
     winners: u128,
     numbers: u128,
@@ -38,7 +46,8 @@ fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
 
 fn parse_numbers(input: &str) -> IResult<&str, u128> {
     fold_many0(
-        preceded(space1, number::<u8>),
+        // Numbers index bits of a u128.
+        preceded(space1, verify(number::<u8>, |&number| number < 128)),
         || 0,
         |acc, number| acc | 1 << number,
     )(input)
@@ -58,15 +67,37 @@ fn parse_cards(input: &str) -> IResult<&str, Vec<Card>> {
     terminated(separated_list1(tag("\n"), parse_card), tag("\n"))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Card> {
-    match all_consuming(parse_cards)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    aoc_core::parse::all(input, parse_cards)
+}
+
+/// `size` scratchcards, none of which win copies of cards past the end
+/// of the table. Like the puzzle's, most win little, or the copies
+/// would soon overflow.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u8> = (1..100).collect();
+    let mut card = 0;
+    aoc_core::generate::lines(rng, size, |rng| {
+        card += 1;
+        let matches = if rng.chance(0.1) {
+            rng.range(0..11)
+        } else {
+            rng.range(0..3)
+        };
+        let matches = matches.min(size - card);
+        rng.shuffle(&mut numbers);
+
+        let (winners, others) = numbers.split_at(10);
+        let mut mine: Vec<u8> = winners[..matches].to_vec();
+        mine.extend(&others[..25 - matches]);
+        rng.shuffle(&mut mine);
+
+        let list = |numbers: &[u8]| {
+            let numbers: Vec<String> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+            numbers.join(" ")
+        };
+        format!("Card {:>3}: {} | {}", card, list(winners), list(&mine))
+    })
 }
 
 pub fn part1(input: &[Card]) -> u32 {
@@ -74,7 +105,7 @@ pub fn part1(input: &[Card]) -> u32 {
 }
 
 pub fn part2(input: &[Card]) -> u32 {
-    let mut clones = [1u32; 187];
+    let mut clones = vec![1u32; input.len()];
     for (game, card) in input.iter().enumerate() {
         let bonus = clones[game];
         let matches = card.matches();
@@ -84,3 +115,44 @@ pub fn part2(input: &[Card]) -> u32 {
     }
     clones.iter().sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn cards() -> impl Strategy<Value = Vec<Card>> {
+        prop::collection::vec(
+            (any::<u128>(), any::<u128>())
+                .prop_map(|(winners, numbers)| Card::new(winners, numbers)),
+            1..20,
+        )
+    }
+
+    fn render(cards: &[Card]) -> String {
+        let numbers = |set: u128| -> String {
+            (0..128)
+                .filter(|bit| set & 1 << bit != 0)
+                .map(|number| format!(" {:>2}", number))
+                .collect()
+        };
+        cards
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| {
+                format!(
+                    "Card {:>3}:{} |{}\n",
+                    idx + 1,
+                    numbers(card.winners),
+                    numbers(card.numbers)
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(cards, |cards| render(cards), parse_input);
+    }
+}
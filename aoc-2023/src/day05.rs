@@ -1,15 +1,19 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use std::{ops::Range, str::FromStr};
 
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{preceded, terminated, tuple},
-    Finish, IResult,
 };
 
+aoc_core::solution! {
+    type Input = Almanac;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
 #[derive(Debug)]
 struct Transform {
     range: Range<u64>,
@@ -41,6 +45,32 @@ impl Map {
             .find_map(|transform| transform.apply(input))
             .unwrap_or(input)
     }
+
+    /// Map whole ranges of inputs at once, splitting them wherever they
+    /// straddle the edge of a transform.
+    fn get_ranges(&self, mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut mapped = Vec::new();
+        while let Some(range) = ranges.pop() {
+            let overlap = self.0.iter().find(|transform| {
+                transform.range.start < range.end && range.start < transform.range.end
+            });
+            let Some(transform) = overlap else {
+                mapped.push(range);
+                continue;
+            };
+
+            let start = range.start.max(transform.range.start);
+            let end = range.end.min(transform.range.end);
+            if range.start < start {
+                ranges.push(range.start..start);
+            }
+            if end < range.end {
+                ranges.push(end..range.end);
+            }
+            mapped.push(transform.apply(start).unwrap()..transform.apply(end - 1).unwrap() + 1);
+        }
+        mapped
+    }
 }
 
 #[derive(Debug)]
@@ -111,15 +141,8 @@ fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Almanac {
-    match all_consuming(parse_almanac)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    aoc_core::parse::all(input, parse_almanac)
 }
 
 pub fn part1(input: &Almanac) -> u64 {
@@ -139,21 +162,98 @@ pub fn part1(input: &Almanac) -> u64 {
         .unwrap()
 }
 
-pub fn part2(_input: &Almanac) -> u64 {
-    0
-    // input
-    //     .seeds
-    //     .chunks(2)
-    //     .flat_map(|range| (range[0]..range[0] + range[1]))
-    //     .map(|seed| {
-    //         let soil = input.seed_to_soil_map.get(seed);
-    //         let fertilizer = input.soil_to_fertilizer_map.get(soil);
-    //         let water = input.fertilizer_to_water.get(fertilizer);
-    //         let light = input.water_to_light.get(water);
-    //         let temperature = input.light_to_temperature.get(light);
-    //         let humidity = input.temperature_to_humidity.get(temperature);
-    //         input.humidity_to_location.get(humidity)
-    //     })
-    //     .min()
-    //     .unwrap()
+pub fn part2(input: &Almanac) -> u64 {
+    let seeds = input
+        .seeds
+        .chunks_exact(2)
+        .map(|range| range[0]..range[0] + range[1])
+        .filter(|range| !range.is_empty())
+        .collect();
+
+    let soil = input.seed_to_soil_map.get_ranges(seeds);
+    let fertilizer = input.soil_to_fertilizer_map.get_ranges(soil);
+    let water = input.fertilizer_to_water.get_ranges(fertilizer);
+    let light = input.water_to_light.get_ranges(water);
+    let temperature = input.light_to_temperature.get_ranges(light);
+    let humidity = input.temperature_to_humidity.get_ranges(temperature);
+    let location = input.humidity_to_location.get_ranges(humidity);
+    location.iter().map(|range| range.start).min().unwrap()
+}
+
+/// Every seed one at a time, which takes minutes on a puzzle input, so
+/// it only checks [`part2`] on small ones.
+#[cfg(test)]
+fn part2_brute_force(input: &Almanac) -> u64 {
+    input
+        .seeds
+        .chunks_exact(2)
+        .flat_map(|range| range[0]..range[0] + range[1])
+        .map(|seed| {
+            let soil = input.seed_to_soil_map.get(seed);
+            let fertilizer = input.soil_to_fertilizer_map.get(soil);
+            let water = input.fertilizer_to_water.get(fertilizer);
+            let light = input.water_to_light.get(water);
+            let temperature = input.light_to_temperature.get(light);
+            let humidity = input.temperature_to_humidity.get(temperature);
+            input.humidity_to_location.get(humidity)
+        })
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A map of a few transforms over disjoint source ranges, like the
+    /// puzzle's.
+    fn map() -> impl Strategy<Value = String> {
+        prop::collection::vec((0..10u64, 1..10u64, 0..100u64), 1..5).prop_map(|transforms| {
+            let mut source = 0;
+            let lines: Vec<String> = transforms
+                .into_iter()
+                .map(|(gap, length, dest)| {
+                    source += gap;
+                    let line = format!("{} {} {}", dest, source, length);
+                    source += length;
+                    line
+                })
+                .collect();
+            lines.join("\n")
+        })
+    }
+
+    fn almanac() -> impl Strategy<Value = String> {
+        let seeds = prop::collection::vec((0..100u64, 1..20u64), 1..4);
+        (seeds, prop::collection::vec(map(), 7)).prop_map(|(seeds, maps)| {
+            let seeds: Vec<String> = seeds
+                .iter()
+                .map(|(start, length)| format!("{} {}", start, length))
+                .collect();
+            let names = [
+                "seed-to-soil",
+                "soil-to-fertilizer",
+                "fertilizer-to-water",
+                "water-to-light",
+                "light-to-temperature",
+                "temperature-to-humidity",
+                "humidity-to-location",
+            ];
+            let maps: String = names
+                .iter()
+                .zip(maps)
+                .map(|(name, map)| format!("\n\n{} map:\n{}", name, map))
+                .collect();
+            format!("seeds: {}{}\n", seeds.join(" "), maps)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn ranges_agree_with_brute_force(input in almanac()) {
+            let almanac = parse_input(&input).unwrap();
+            prop_assert_eq!(part2(&almanac), part2_brute_force(&almanac));
+        }
+    }
 }
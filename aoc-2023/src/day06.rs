@@ -1,14 +1,18 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
-    bytes::complete::tag,
     character::complete::{digit1, space1},
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{preceded, separated_pair, terminated},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Races;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
 #[derive(Debug)]
 pub struct Races {
     times: Vec<u64>,
@@ -68,15 +72,8 @@ fn parse_races(input: &str) -> IResult<&str, Races> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Races {
-    match all_consuming(parse_races)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Races, ParseError> {
+    aoc_core::parse::all(input, parse_races)
 }
 
 fn simulate(time: u64, distance: u64) -> usize {
@@ -1,14 +1,23 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::{tag, take_while_m_n},
+    bytes::complete::take_while_m_n,
     character::complete::{digit1, space1},
-    combinator::{all_consuming, map, map_res},
-    error::Error,
+    combinator::{map, map_res},
     multi::separated_list1,
     sequence::{separated_pair, terminated},
-    Finish, IResult,
 };
 use std::str::FromStr;
 
+aoc_core::solution! {
+    type Input = Vec<Hand>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 1000 };
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Score {
     HighCard,
@@ -58,15 +67,28 @@ fn parse_hands(input: &str) -> IResult<&str, Vec<Hand>> {
     terminated(separated_list1(tag("\n"), parse_hand), tag("\n"))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Hand> {
-    match all_consuming(parse_hands)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Hand>, ParseError> {
+    aoc_core::parse::all(input, parse_hands)
+}
+
+/// `size` different hands and their bids.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+
+    let mut seen = std::collections::HashSet::new();
+    aoc_core::generate::lines(rng, size, |rng| {
+        let hand = loop {
+            let hand: Vec<u8> = (0..5).map(|_| *rng.pick(CARDS)).collect();
+            if seen.insert(hand.clone()) {
+                break hand;
+            }
+        };
+        format!(
+            "{} {}",
+            String::from_utf8(hand).unwrap(),
+            rng.range(1..1000)
+        )
+    })
 }
 
 #[derive(Debug, PartialEq, Eq)]
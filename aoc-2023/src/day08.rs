@@ -1,13 +1,21 @@
+use aoc_core::{
+    numtheory,
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while_m_n},
-    combinator::{all_consuming, map},
-    error::Error,
+    bytes::complete::take_while_m_n,
+    combinator::map,
     multi::{fold_many1, many1},
     sequence::{separated_pair, terminated, tuple},
-    Finish, IResult,
 };
 
+aoc_core::solution! {
+    type Input = Map;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
 pub type Network = Vec<Option<(u16, u16)>>;
 
 #[derive(Debug)]
@@ -77,15 +85,8 @@ fn parse_map(input: &str) -> IResult<&str, Map> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Map {
-    match all_consuming(parse_map)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    aoc_core::parse::all(input, parse_map)
 }
 
 fn traverse(map: &Map, mut position: usize) -> u64 {
@@ -115,23 +116,7 @@ pub fn part2(input: &Map) -> u64 {
         .filter_map(|(position, &edges)| {
             (position % 26 == 0 && edges.is_some()).then_some(position)
         })
-        .fold(None, |max_moves, position| {
-            let moves = traverse(input, position);
-            match max_moves {
-                None => Some(moves),
-                Some(max_moves) => Some(lcd(max_moves, moves)),
-            }
-        })
+        .map(|position| traverse(input, position))
+        .reduce(numtheory::lcm)
         .unwrap()
 }
-
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    while b != 0 {
-        (a, b) = (b, a % b);
-    }
-    a
-}
-
-fn lcd(a: u64, b: u64) -> u64 {
-    a / gcd(a, b) * b
-}
@@ -1,14 +1,22 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::{tag, IResult, ParseError},
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::digit1,
-    combinator::{all_consuming, map_res, opt, recognize},
-    error::Error,
+    combinator::{map_res, opt, recognize},
     multi::separated_list1,
     sequence::{terminated, tuple},
-    Finish, IResult,
 };
 use std::{borrow::Cow, str::FromStr};
 
+aoc_core::solution! {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+    const GENERATOR = Generator { generate, size: 200 };
+}
+
 fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
     map_res(recognize(tuple((opt(tag("-")), digit1))), FromStr::from_str)(input)
 }
@@ -21,15 +29,28 @@ fn parse_sequences(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
     terminated(separated_list1(tag("\n"), parse_sequence), tag("\n"))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    match all_consuming(parse_sequences)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    aoc_core::parse::all(input, parse_sequences)
+}
+
+/// `size` histories of 21 values, each the sum of a few layers of
+/// differences that end in a constant one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::lines(rng, size, |rng| {
+        let depth = rng.range(1..8);
+        let mut firsts: Vec<i32> = (0..=depth).map(|_| rng.range(-9..10)).collect();
+
+        let history: Vec<String> = (0..21)
+            .map(|_| {
+                let value = firsts[0];
+                for layer in 0..depth {
+                    firsts[layer] += firsts[layer + 1];
+                }
+                value.to_string()
+            })
+            .collect();
+        history.join(" ")
+    })
 }
 
 fn deltas(sequence: &[i32]) -> impl Iterator<Item = i32> + '_ {
@@ -77,3 +98,29 @@ pub fn part2(input: &[Vec<i32>]) -> i32 {
         .map(|sequence| predict_backwards(sequence))
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn histories() -> impl Strategy<Value = Vec<Vec<i32>>> {
+        prop::collection::vec(prop::collection::vec(-1000..1000, 1..22), 1..20)
+    }
+
+    fn render(histories: &[Vec<i32>]) -> String {
+        histories
+            .iter()
+            .map(|history| {
+                let values: Vec<String> = history.iter().map(i32::to_string).collect();
+                values.join(" ") + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(histories, |histories| render(histories), parse_input);
+    }
+}
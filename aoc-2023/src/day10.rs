@@ -1,4 +1,10 @@
-const WIDTH: usize = 140;
+use aoc_core::{grid::Grid, parse::ParseError};
+
+aoc_core::solution! {
+    type Input = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Tile {
@@ -12,39 +18,18 @@ pub enum Tile {
     BendSW,
 }
 
-pub fn parse_input(input: &str) -> Vec<Tile> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.bytes().map(|c| match c {
-                b'S' => Tile::Start,
-                b'.' => Tile::Ground,
-                b'|' => Tile::Vertical,
-                b'-' => Tile::Horizonal,
-                b'L' => Tile::BendNE,
-                b'J' => Tile::BendNW,
-                b'F' => Tile::BendSE,
-                b'7' => Tile::BendSW,
-                _ => unreachable!(),
-            })
-        })
-        .collect()
-}
-
-fn up(point: usize, width: usize) -> Option<usize> {
-    point.checked_sub(width)
-}
-
-fn left(point: usize, width: usize) -> Option<usize> {
-    point.checked_sub(1).filter(|p| p % width != width - 1)
-}
-
-fn down(point: usize, width: usize) -> Option<usize> {
-    point.checked_add(width).filter(|&p| p < width * width)
-}
-
-fn right(point: usize, width: usize) -> Option<usize> {
-    point.checked_add(1).filter(|p| p % width != 0)
+pub fn parse_input(input: &str) -> Result<Grid<Tile>, ParseError> {
+    aoc_core::parse::grid(input, "pipe", |c| match c {
+        b'S' => Some(Tile::Start),
+        b'.' => Some(Tile::Ground),
+        b'|' => Some(Tile::Vertical),
+        b'-' => Some(Tile::Horizonal),
+        b'L' => Some(Tile::BendNE),
+        b'J' => Some(Tile::BendNW),
+        b'F' => Some(Tile::BendSE),
+        b'7' => Some(Tile::BendSW),
+        _ => None,
+    })
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -55,53 +40,70 @@ enum Direction {
     West,
 }
 
-fn try_step(map: &[Tile], (heading, position): (Direction, usize)) -> Option<(Direction, usize)> {
-    match heading {
-        Direction::North => {
-            let next_position = up(position, WIDTH)?;
-            let next_heading = match map[next_position] {
-                Tile::Vertical => Some(Direction::North),
-                Tile::BendSE => Some(Direction::East),
-                Tile::BendSW => Some(Direction::West),
-                _ => None,
-            }?;
-            Some((next_heading, next_position))
-        }
-        Direction::East => {
-            let next_position = right(position, WIDTH)?;
-            let next_heading = match map[next_position] {
-                Tile::Horizonal => Some(Direction::East),
-                Tile::BendNW => Some(Direction::North),
-                Tile::BendSW => Some(Direction::South),
-                _ => None,
-            }?;
-            Some((next_heading, next_position))
-        }
-        Direction::South => {
-            let next_position = down(position, WIDTH)?;
-            let next_heading = match map[next_position] {
-                Tile::Vertical => Some(Direction::South),
-                Tile::BendNE => Some(Direction::East),
-                Tile::BendNW => Some(Direction::West),
-                _ => None,
-            }?;
-            Some((next_heading, next_position))
-        }
-        Direction::West => {
-            let next_position = left(position, WIDTH)?;
-            let next_heading = match map[next_position] {
-                Tile::Horizonal => Some(Direction::West),
-                Tile::BendNE => Some(Direction::North),
-                Tile::BendSE => Some(Direction::South),
-                _ => None,
-            }?;
-            Some((next_heading, next_position))
+impl Direction {
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
         }
     }
 }
 
-pub fn part1(map: &[Tile]) -> usize {
-    let start = map.iter().position(|&tile| tile == Tile::Start).unwrap();
+type Position = (usize, usize);
+
+fn try_step(
+    map: &Grid<Tile>,
+    (heading, (x, y)): (Direction, Position),
+) -> Option<(Direction, Position)> {
+    let (dx, dy) = heading.offset();
+    let next_position = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+    let next_heading = match (heading, map.get(next_position.0, next_position.1)?) {
+        (Direction::North, Tile::Vertical) => Direction::North,
+        (Direction::North, Tile::BendSE) => Direction::East,
+        (Direction::North, Tile::BendSW) => Direction::West,
+        (Direction::East, Tile::Horizonal) => Direction::East,
+        (Direction::East, Tile::BendNW) => Direction::North,
+        (Direction::East, Tile::BendSW) => Direction::South,
+        (Direction::South, Tile::Vertical) => Direction::South,
+        (Direction::South, Tile::BendNE) => Direction::East,
+        (Direction::South, Tile::BendNW) => Direction::West,
+        (Direction::West, Tile::Horizonal) => Direction::West,
+        (Direction::West, Tile::BendNE) => Direction::North,
+        (Direction::West, Tile::BendSE) => Direction::South,
+        _ => return None,
+    };
+    Some((next_heading, next_position))
+}
+
+fn find_start(map: &Grid<Tile>) -> Position {
+    let start = map.cells().iter().position(|&tile| tile == Tile::Start);
+    map.position_of(start.unwrap())
+}
+
+/// The pipe hidden under the start, going by the pipes it connects to.
+fn start_pipe(map: &Grid<Tile>, start: Position) -> Tile {
+    let connects = |heading| try_step(map, (heading, start)).is_some();
+    match [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ]
+    .map(connects)
+    {
+        [true, true, _, _] => Tile::BendNE,
+        [true, _, true, _] => Tile::Vertical,
+        [true, _, _, true] => Tile::BendNW,
+        [_, true, true, _] => Tile::BendSE,
+        [_, true, _, true] => Tile::Horizonal,
+        _ => Tile::BendSW,
+    }
+}
+
+pub fn part1(map: &Grid<Tile>) -> usize {
+    let start = find_start(map);
     let mut cursors: Vec<_> = [
         try_step(map, (Direction::North, start)),
         try_step(map, (Direction::East, start)),
@@ -122,10 +124,10 @@ pub fn part1(map: &[Tile]) -> usize {
     steps
 }
 
-pub fn part2(map: &[Tile]) -> usize {
-    let mut border = vec![false; map.len()];
+pub fn part2(map: &Grid<Tile>) -> usize {
+    let mut border = map.map(|_| false);
 
-    let start = map.iter().position(|&tile| tile == Tile::Start).unwrap();
+    let start = find_start(map);
     let mut cursors: Vec<_> = [
         try_step(map, (Direction::North, start)),
         try_step(map, (Direction::East, start)),
@@ -148,17 +150,23 @@ pub fn part2(map: &[Tile]) -> usize {
         }
     }
 
+    let start_pipe = start_pipe(map, start);
     let mut counter = 0;
-    for (edges, tiles) in border.chunks(WIDTH).zip(map.chunks(WIDTH)) {
+    for (edges, tiles) in border.rows().zip(map.rows()) {
         let mut inside = false;
         let mut direction = None;
         for (&edge, &tile) in edges.iter().zip(tiles.iter()) {
             if edge {
+                let tile = if tile == Tile::Start {
+                    start_pipe
+                } else {
+                    tile
+                };
                 match tile {
                     Tile::Vertical => {
                         inside = !inside;
                     }
-                    Tile::Start | Tile::BendNE => {
+                    Tile::BendNE => {
                         inside = !inside;
                         direction = Some(Direction::North);
                     }
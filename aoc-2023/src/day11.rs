@@ -1,4 +1,15 @@
-const WIDTH: usize = 140;
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::ParseError,
+};
+
+aoc_core::solution! {
+    type Input = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 140 };
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Tile {
@@ -6,58 +17,62 @@ pub enum Tile {
     Empty,
 }
 
-pub fn parse_input(input: &str) -> Vec<Tile> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.bytes().map(|c| match c {
-                b'#' => Tile::Galaxy,
-                b'.' => Tile::Empty,
-                _ => unreachable!(),
-            })
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Grid<Tile>, ParseError> {
+    aoc_core::parse::grid(input, "'#' or '.'", |c| match c {
+        b'#' => Some(Tile::Galaxy),
+        b'.' => Some(Tile::Empty),
+        _ => None,
+    })
 }
 
-fn expansion_prefix_sums_x(map: &[Tile], scale: usize) -> impl Iterator<Item = usize> + '_ {
-    (0..WIDTH)
-        .map(|x| (0..WIDTH).all(|y| map[WIDTH * y + x] == Tile::Empty))
-        .scan(0, move |dist, empty| {
-            *dist += if empty { scale } else { 1 };
-            Some(*dist)
-        })
-}
+/// A `size` by `size` image of sparse galaxies, with some rows and
+/// columns left empty to expand.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.08)).collect();
 
-fn galaxies_x<'a>(
-    map: &'a [Tile],
-    prefix_sums: &'a [usize],
-) -> impl Iterator<Item = (usize, usize)> + 'a {
-    (0..WIDTH).filter_map(move |x| {
-        let count = (0..WIDTH)
-            .filter(|y| map[WIDTH * y + x] == Tile::Galaxy)
-            .count();
-        (count > 0).then_some((prefix_sums[x], count))
-    })
+    let mut out = String::new();
+    for _ in 0..size {
+        let empty_row = rng.chance(0.08);
+        for &empty_column in &empty_columns {
+            let galaxy = !empty_row && !empty_column && rng.chance(0.03);
+            out.push(if galaxy { '#' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
 }
 
-fn expansion_prefix_sums_y(map: &[Tile], scale: usize) -> impl Iterator<Item = usize> + '_ {
-    (0..WIDTH)
-        .map(|y| (0..WIDTH).all(|x| map[WIDTH * y + x] == Tile::Empty))
+/// The distance to each of `lines` from the start, once the empty ones
+/// have grown `scale` times as wide.
+fn expansion_prefix_sums<'a, L>(lines: L, scale: usize) -> impl Iterator<Item = usize>
+where
+    L: Iterator,
+    L::Item: IntoIterator<Item = &'a Tile>,
+{
+    lines
+        .map(|line| line.into_iter().all(|&tile| tile == Tile::Empty))
         .scan(0, move |dist, empty| {
             *dist += if empty { scale } else { 1 };
             Some(*dist)
         })
 }
 
-fn galaxies_y<'a>(
-    map: &'a [Tile],
+/// How far along and how many galaxies there are in each of `lines` that
+/// has any.
+fn galaxy_counts<'a, L>(
+    lines: L,
     prefix_sums: &'a [usize],
-) -> impl Iterator<Item = (usize, usize)> + 'a {
-    (0..WIDTH).filter_map(move |y| {
-        let count = (0..WIDTH)
-            .filter(|x| map[WIDTH * y + x] == Tile::Galaxy)
+) -> impl Iterator<Item = (usize, usize)> + 'a
+where
+    L: Iterator + 'a,
+    L::Item: IntoIterator<Item = &'a Tile>,
+{
+    lines.enumerate().filter_map(move |(idx, line)| {
+        let count = line
+            .into_iter()
+            .filter(|&&tile| tile == Tile::Galaxy)
             .count();
-        (count > 0).then_some((prefix_sums[y], count))
+        (count > 0).then_some((prefix_sums[idx], count))
     })
 }
 
@@ -65,12 +80,12 @@ fn pairs<T: Copy>(slice: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
     (0..slice.len()).flat_map(move |i| (i + 1..slice.len()).map(move |j| (slice[i], slice[j])))
 }
 
-pub fn solution(map: &[Tile], scale: usize) -> usize {
-    let mut prefix_sums = Vec::with_capacity(140);
-    let mut galaxies = Vec::with_capacity(140);
+pub fn solution(map: &Grid<Tile>, scale: usize) -> usize {
+    let mut prefix_sums = Vec::with_capacity(map.width());
+    let mut galaxies = Vec::with_capacity(map.width());
 
-    prefix_sums.extend(expansion_prefix_sums_x(map, scale));
-    galaxies.extend(galaxies_x(map, &prefix_sums));
+    prefix_sums.extend(expansion_prefix_sums(map.columns(), scale));
+    galaxies.extend(galaxy_counts(map.columns(), &prefix_sums));
     let dx = pairs(&galaxies)
         .map(|((d1, count1), (d2, count2))| (d2 - d1) * count1 * count2)
         .sum::<usize>();
@@ -78,8 +93,8 @@ pub fn solution(map: &[Tile], scale: usize) -> usize {
     prefix_sums.clear();
     galaxies.clear();
 
-    prefix_sums.extend(expansion_prefix_sums_y(map, scale));
-    galaxies.extend(galaxies_y(map, &prefix_sums));
+    prefix_sums.extend(expansion_prefix_sums(map.rows(), scale));
+    galaxies.extend(galaxy_counts(map.rows(), &prefix_sums));
     let dy = pairs(&galaxies)
         .map(|((d1, count1), (d2, count2))| (d2 - d1) * count1 * count2)
         .sum::<usize>();
@@ -87,10 +102,10 @@ pub fn solution(map: &[Tile], scale: usize) -> usize {
     dx + dy
 }
 
-pub fn part1(map: &[Tile]) -> usize {
+pub fn part1(map: &Grid<Tile>) -> usize {
     solution(map, 2)
 }
 
-pub fn part2(map: &[Tile]) -> usize {
+pub fn part2(map: &Grid<Tile>) -> usize {
     solution(map, 1_000_000)
 }
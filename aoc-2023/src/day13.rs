@@ -1,88 +1,47 @@
-use nom::{
-    bytes::complete::{tag, take_while1},
-    combinator::{all_consuming, map},
-    error::Error,
-    multi::separated_list1,
-    sequence::terminated,
-    Finish, IResult,
+use aoc_core::{
+    grid::Grid,
+    parse::{tag, IResult, ParseError},
 };
+use nom::{combinator::verify, multi::separated_list1, sequence::terminated};
 
-#[derive(Debug)]
-pub struct Grid {
-    grid: Vec<u8>,
-    width: usize,
-    height: usize,
+aoc_core::solution! {
+    type Input = Vec<Grid<char>>;
+    type Part1 = usize;
+    type Part2 = usize;
 }
 
-impl Grid {
-    fn new(grid: Vec<u8>, width: usize, height: usize) -> Self {
-        Self {
-            grid,
-            width,
-            height,
-        }
-    }
-
-    fn slice(&self, horizontal: &mut Vec<u64>, vertical: &mut Vec<u64>) {
-        horizontal.clear();
-        horizontal.resize(self.height, 0);
+/// Slice `pattern` into bitsets of its rows and columns, with a bit set
+/// for every rock.
+fn slice(pattern: &Grid<char>, horizontal: &mut Vec<u64>, vertical: &mut Vec<u64>) {
+    horizontal.clear();
+    horizontal.resize(pattern.height(), 0);
 
-        vertical.clear();
-        vertical.resize(self.width, 0);
+    vertical.clear();
+    vertical.resize(pattern.width(), 0);
 
-        for (position, &point) in self.grid.iter().enumerate() {
-            if point == b'#' {
-                let (x, y) = (position % self.width, position / self.width);
-                vertical[x] |= 1 << y;
-                horizontal[y] |= 1 << x;
-            }
+    for ((x, y), &point) in pattern.iter() {
+        if point == '#' {
+            vertical[x] |= 1 << y;
+            horizontal[y] |= 1 << x;
         }
     }
 }
 
-pub fn grid(gridset: &str) -> impl Fn(&str) -> IResult<&str, Grid> {
-    let set = gridset.chars().collect::<Vec<_>>();
-
-    move |input: &str| {
-        let mut width = None;
-
-        let (input, lines) = separated_list1(
-            tag("\n"),
-            map(take_while1(|c| set.contains(&c)), |s: &str| {
-                if let Some(width) = width {
-                    assert_eq!(width, s.len());
-                } else {
-                    width = Some(s.len());
-                }
-                s.as_bytes()
-            }),
-        )(input)?;
-
-        let grid = lines.into_iter().flatten().copied().collect::<Vec<u8>>();
-        let width = width.unwrap();
-        let height = grid.len() / width;
-
-        Ok((input, Grid::new(grid, width, height)))
-    }
-}
-
-fn parse_grids(input: &str) -> IResult<&str, Vec<Grid>> {
-    terminated(separated_list1(tag("\n\n"), grid("#.")), tag("\n"))(input)
+fn parse_grids(input: &str) -> IResult<&str, Vec<Grid<char>>> {
+    // Rows and columns are sliced into u64 bitsets.
+    let pattern = verify(
+        aoc_core::grid::parser(|c| matches!(c, '#' | '.').then_some(c)),
+        |pattern: &Grid<char>| pattern.width() <= 64 && pattern.height() <= 64,
+    );
+    terminated(separated_list1(tag("\n\n"), pattern), tag("\n"))(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Grid> {
-    match all_consuming(parse_grids)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    aoc_core::parse::all(input, parse_grids)
 }
 
 pub fn summarize_patterns(
-    input: &[Grid],
+    input: &[Grid<char>],
     find_reflection_fn: fn(&[u64]) -> Option<usize>,
 ) -> usize {
     let mut horizontal = Vec::with_capacity(64);
@@ -90,8 +49,8 @@ pub fn summarize_patterns(
 
     input
         .iter()
-        .flat_map(|grid| {
-            grid.slice(&mut horizontal, &mut vertical);
+        .flat_map(|pattern| {
+            slice(pattern, &mut horizontal, &mut vertical);
             find_reflection_fn(&horizontal)
                 .map(|score| score * 100)
                 .or_else(|| find_reflection_fn(&vertical))
@@ -99,20 +58,16 @@ pub fn summarize_patterns(
         .sum()
 }
 
-pub fn part1(input: &[Grid]) -> usize {
+pub fn part1(input: &[Grid<char>]) -> usize {
     summarize_patterns(input, |slices| {
-        (1..slices.len()).find_map(|pivot| {
+        (1..slices.len()).find(|&pivot| {
             let (left, right) = slices.split_at(pivot);
-            left.iter()
-                .rev()
-                .zip(right)
-                .all(|(&a, &b)| a == b)
-                .then_some(pivot)
+            left.iter().rev().zip(right).all(|(&a, &b)| a == b)
         })
     })
 }
 
-pub fn part2(input: &[Grid]) -> usize {
+pub fn part2(input: &[Grid<char>]) -> usize {
     summarize_patterns(input, |slices| {
         'outer: for pivot in 1..slices.len() {
             let (left, right) = slices.split_at(pivot);
@@ -133,3 +88,31 @@ pub fn part2(input: &[Grid]) -> usize {
         None
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn pattern() -> impl Strategy<Value = Grid<char>> {
+        (1..20usize, 1..20usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::sample::select(&['#', '.'][..]), width * height)
+                .prop_map(move |points| Grid::new(points, width))
+        })
+    }
+
+    fn render(patterns: &[Grid<char>]) -> String {
+        let patterns: Vec<String> = patterns.iter().map(Grid::to_string).collect();
+        patterns.join("\n")
+    }
+
+    fn patterns() -> impl Strategy<Value = Vec<Grid<char>>> {
+        prop::collection::vec(pattern(), 1..5)
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(patterns, |patterns| render(patterns), parse_input);
+    }
+}
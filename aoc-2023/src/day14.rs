@@ -1,6 +1,17 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    grid::Grid,
+    parse::ParseError,
+};
 use std::collections::BTreeMap;
 
-const WIDTH: usize = 100;
+aoc_core::solution! {
+    type Input = Grid<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 100 };
+}
+
 const LIMIT: usize = 1_000_000_000;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -10,28 +21,33 @@ pub enum Tile {
     Cube,
 }
 
-pub fn parse_input(input: &str) -> Vec<Tile> {
-    input
-        .lines()
-        .flat_map(|line| {
-            line.bytes().map(|c| match c {
-                b'.' => Tile::Empty,
-                b'O' => Tile::Round,
-                b'#' => Tile::Cube,
-                _ => unreachable!(),
-            })
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Grid<Tile>, ParseError> {
+    aoc_core::parse::grid(input, "'.', 'O' or '#'", |c| match c {
+        b'.' => Some(Tile::Empty),
+        b'O' => Some(Tile::Round),
+        b'#' => Some(Tile::Cube),
+        _ => None,
+    })
+}
+
+/// A `size` by `size` platform, with about as many rocks as the puzzle's.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    aoc_core::generate::grid(rng, size, size, |rng| match rng.below(100) {
+        0..20 => 'O',
+        20..36 => '#',
+        _ => '.',
+    })
 }
 
-fn tilt_north(map: &mut [Tile]) {
-    for x in 0..WIDTH {
+fn tilt_north(map: &mut Grid<Tile>) {
+    let (width, height) = (map.width(), map.height());
+    let cells = map.cells_mut();
+    for x in 0..width {
         let mut stop = 0;
-        for y in 0..WIDTH {
-            let c = map[y * WIDTH + x];
-            match c {
+        for y in 0..height {
+            match cells[y * width + x] {
                 Tile::Round => {
-                    map.swap(y * WIDTH + x, stop * WIDTH + x);
+                    cells.swap(y * width + x, stop * width + x);
                     stop += 1;
                 }
                 Tile::Cube => {
@@ -43,14 +59,15 @@ fn tilt_north(map: &mut [Tile]) {
     }
 }
 
-fn tilt_west(map: &mut [Tile]) {
-    for y in 0..WIDTH {
+fn tilt_west(map: &mut Grid<Tile>) {
+    let (width, height) = (map.width(), map.height());
+    let cells = map.cells_mut();
+    for y in 0..height {
         let mut stop = 0;
-        for x in 0..WIDTH {
-            let c = map[y * WIDTH + x];
-            match c {
+        for x in 0..width {
+            match cells[y * width + x] {
                 Tile::Round => {
-                    map.swap(y * WIDTH + x, y * WIDTH + stop);
+                    cells.swap(y * width + x, y * width + stop);
                     stop += 1;
                 }
                 Tile::Cube => {
@@ -62,14 +79,15 @@ fn tilt_west(map: &mut [Tile]) {
     }
 }
 
-fn tilt_south(map: &mut [Tile]) {
-    for x in 0..WIDTH {
-        let mut stop = WIDTH - 1;
-        for y in (0..WIDTH).rev() {
-            let c = map[y * WIDTH + x];
-            match c {
+fn tilt_south(map: &mut Grid<Tile>) {
+    let (width, height) = (map.width(), map.height());
+    let cells = map.cells_mut();
+    for x in 0..width {
+        let mut stop = height - 1;
+        for y in (0..height).rev() {
+            match cells[y * width + x] {
                 Tile::Round => {
-                    map.swap(y * WIDTH + x, stop * WIDTH + x);
+                    cells.swap(y * width + x, stop * width + x);
                     stop = stop.wrapping_sub(1);
                 }
                 Tile::Cube => {
@@ -81,14 +99,15 @@ fn tilt_south(map: &mut [Tile]) {
     }
 }
 
-fn tilt_east(map: &mut [Tile]) {
-    for y in 0..WIDTH {
-        let mut stop = WIDTH - 1;
-        for x in (0..WIDTH).rev() {
-            let c = map[y * WIDTH + x];
-            match c {
+fn tilt_east(map: &mut Grid<Tile>) {
+    let (width, height) = (map.width(), map.height());
+    let cells = map.cells_mut();
+    for y in 0..height {
+        let mut stop = width - 1;
+        for x in (0..width).rev() {
+            match cells[y * width + x] {
                 Tile::Round => {
-                    map.swap(y * WIDTH + x, y * WIDTH + stop);
+                    cells.swap(y * width + x, y * width + stop);
                     stop = stop.wrapping_sub(1);
                 }
                 Tile::Cube => {
@@ -100,23 +119,21 @@ fn tilt_east(map: &mut [Tile]) {
     }
 }
 
-fn calculate_load(map: &[Tile]) -> usize {
-    map.chunks(WIDTH)
-        .zip((1..WIDTH + 1).rev())
-        .map(|(chunk, distance)| {
-            chunk.iter().filter(|&&tile| tile == Tile::Round).count() * distance
-        })
+fn calculate_load(map: &Grid<Tile>) -> usize {
+    map.rows()
+        .zip((1..map.height() + 1).rev())
+        .map(|(row, distance)| row.iter().filter(|&&tile| tile == Tile::Round).count() * distance)
         .sum()
 }
 
-pub fn part1(map: &[Tile]) -> usize {
-    let mut map = map.to_vec();
+pub fn part1(map: &Grid<Tile>) -> usize {
+    let mut map = map.clone();
     tilt_north(&mut map);
     calculate_load(&map)
 }
 
-pub fn part2(map: &[Tile]) -> usize {
-    let mut map = map.to_vec();
+pub fn part2(map: &Grid<Tile>) -> usize {
+    let mut map = map.clone();
     let mut seen = BTreeMap::new();
 
     for i in 1.. {
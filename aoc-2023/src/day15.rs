@@ -1,11 +1,73 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
 use std::num::NonZeroU16;
 
-pub fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    input
+aoc_core::solution! {
+    type Input = Vec<Vec<u8>>;
+    type Part1 = u32;
+    type Part2 = usize;
+    const GENERATOR = Generator { generate, size: 4000 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    Ok(input
         .trim_end()
         .split(',')
         .map(|s| s.bytes().collect::<Vec<_>>())
-        .collect()
+        .collect())
+}
+
+/// `size` steps. Like the puzzle's, labels differ in their last two
+/// letters and boxes never hold more than six lenses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut suffixes: Vec<[u8; 2]> = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| [a, b]))
+        .collect();
+    rng.shuffle(&mut suffixes);
+
+    let labels: Vec<Vec<u8>> = suffixes[..500]
+        .iter()
+        .map(|suffix| {
+            let mut label: Vec<u8> = (0..rng.range(0..5))
+                .map(|_| rng.range(b'a'..b'z' + 1))
+                .collect();
+            label.extend(suffix);
+            label
+        })
+        .collect();
+
+    let mut boxes = [0u8; 256];
+    let mut placed = vec![false; labels.len()];
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let idx = rng.below(labels.len() as u64) as usize;
+            let label = &labels[idx];
+            let hash = hash_and_label(label).0 as usize;
+            let name = String::from_utf8(label.clone()).unwrap();
+
+            let remove = if placed[idx] {
+                rng.chance(0.4)
+            } else {
+                boxes[hash] == 6 || rng.chance(0.3)
+            };
+            if remove {
+                if placed[idx] {
+                    placed[idx] = false;
+                    boxes[hash] -= 1;
+                }
+                name + "-"
+            } else {
+                if !placed[idx] {
+                    placed[idx] = true;
+                    boxes[hash] += 1;
+                }
+                format!("{}={}", name, rng.range(1..10))
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
 }
 
 #[inline(always)]
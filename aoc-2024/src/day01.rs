@@ -1,18 +1,28 @@
+use aoc_core::parse::ParseError;
+#[cfg(feature = "simd")]
 use std::simd::{cmp::SimdPartialOrd, u32x8};
 
-pub fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
+aoc_core::solution! {
+    type Input = (Vec<u32>, Vec<u32>);
+    type Part1 = u32;
+    type Part2 = u32;
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
     let mut left_list = vec![];
     let mut right_list = vec![];
 
     for line in input.lines() {
-        let (left, right) = line.split_once("   ").unwrap();
-        left_list.push(left.parse().unwrap());
-        right_list.push(right.parse().unwrap());
+        let (left, right) = line
+            .split_once("   ")
+            .ok_or_else(|| ParseError::new(input, line, "two location IDs"))?;
+        left_list.push(aoc_core::parse::number(input, left)?);
+        right_list.push(aoc_core::parse::number(input, right)?);
     }
 
     left_list.sort_unstable();
     right_list.sort_unstable();
-    (left_list, right_list)
+    Ok((left_list, right_list))
 }
 
 pub fn part1((left, right): &(Vec<u32>, Vec<u32>)) -> u32 {
@@ -34,22 +44,9 @@ pub fn part2((left, right): &(Vec<u32>, Vec<u32>)) -> u32 {
     let mut acc = 0;
     let mut cursor = 0;
     for (right_value, frequency) in frequencies {
-        let right_simd = u32x8::splat(right_value as u32);
-        while cursor + u32x8::LEN <= left.len() {
-            let chunk = u32x8::from_slice(&left[cursor..]);
-            let mask = chunk.simd_ge(right_simd).to_bitmask();
-            if mask != 0 {
-                cursor += mask.trailing_zeros() as usize;
-                break;
-            }
-            cursor += u32x8::LEN;
-        }
+        cursor = skip_below(left, cursor, right_value);
 
-        while cursor < left.len() && left[cursor] < right_value {
-            cursor += 1;
-        }
-
-        if cursor < left.len() && left[cursor] == right_value {
+        while cursor < left.len() && left[cursor] == right_value {
             acc += right_value * frequency;
             cursor += 1;
         }
@@ -60,3 +57,29 @@ pub fn part2((left, right): &(Vec<u32>, Vec<u32>)) -> u32 {
     }
     acc
 }
+
+/// Moves `cursor` past every value of the sorted `list` below `value`.
+#[cfg(not(feature = "simd"))]
+fn skip_below(list: &[u32], cursor: usize, value: u32) -> usize {
+    cursor + list[cursor..].partition_point(|&v| v < value)
+}
+
+/// Moves `cursor` past every value of the sorted `list` below `value`,
+/// eight at a time.
+#[cfg(feature = "simd")]
+fn skip_below(list: &[u32], mut cursor: usize, value: u32) -> usize {
+    let value_simd = u32x8::splat(value);
+    while cursor + u32x8::LEN <= list.len() {
+        let chunk = u32x8::from_slice(&list[cursor..]);
+        let mask = chunk.simd_ge(value_simd).to_bitmask();
+        if mask != 0 {
+            return cursor + mask.trailing_zeros() as usize;
+        }
+        cursor += u32x8::LEN;
+    }
+
+    while cursor < list.len() && list[cursor] < value {
+        cursor += 1;
+    }
+    cursor
+}
@@ -1,9 +1,17 @@
-pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
+use aoc_core::parse::ParseError;
+
+aoc_core::solution! {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = usize;
+    type Part2 = i32;
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     input
         .lines()
         .map(|line| {
             line.split(' ')
-                .map(|num| num.parse::<i32>().unwrap())
+                .map(|num| aoc_core::parse::number(input, num))
                 .collect()
         })
         .collect()
@@ -70,3 +78,29 @@ pub fn part2(grid: &[Vec<i32>]) -> i32 {
     }
     matches
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn reports() -> impl Strategy<Value = Vec<Vec<i32>>> {
+        prop::collection::vec(prop::collection::vec(0..100, 1..10), 1..20)
+    }
+
+    fn render(reports: &[Vec<i32>]) -> String {
+        reports
+            .iter()
+            .map(|report| {
+                let levels: Vec<String> = report.iter().map(i32::to_string).collect();
+                levels.join(" ") + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(reports, |reports| render(reports), parse_input);
+    }
+}
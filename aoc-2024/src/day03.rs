@@ -1,14 +1,18 @@
+use aoc_core::parse::{tag, IResult, ParseError};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{anychar, char, digit1},
-    combinator::{all_consuming, map, map_res, recognize},
-    error::Error,
-    multi::{fold_many0, many0},
-    sequence::{delimited, preceded, separated_pair, terminated, tuple},
-    Finish, IResult,
+    combinator::{map, map_res, recognize},
+    multi::fold_many0,
+    sequence::{delimited, preceded, separated_pair},
 };
 
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = u32;
+    type Part2 = u32;
+}
+
 #[derive(Debug)]
 pub enum Op {
     Mul(u32, u32),
@@ -39,7 +43,7 @@ fn parse_mul(input: &str) -> IResult<&str, Op> {
 pub fn parse_opts(input: &str) -> IResult<&str, Vec<Op>> {
     fold_many0(
         alt((
-            map(parse_mul, |parsed| Some(parsed)),
+            map(parse_mul, Some),
             map(tag("do()"), |_| Some(Op::Do)),
             map(tag("don't()"), |_| Some(Op::Dont)),
             map(anychar, |_| None),
@@ -54,15 +58,8 @@ pub fn parse_opts(input: &str) -> IResult<&str, Vec<Op>> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> Vec<Op> {
-    match all_consuming(parse_opts)(input).finish() {
-        Ok((_, output)) => Ok(output),
-        Err(Error { input, code }) => Err(Error {
-            input: input.to_string(),
-            code,
-        }),
-    }
-    .unwrap()
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
+    aoc_core::parse::all(input, parse_opts)
 }
 
 pub fn part1(input: &[Op]) -> u32 {
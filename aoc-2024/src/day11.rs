@@ -1,12 +1,31 @@
+use aoc_core::{
+    generate::{Generator, Rng},
+    parse::ParseError,
+};
 use std::collections::HashMap;
 
-pub fn parse_input(input: &str) -> Vec<u64> {
+aoc_core::solution! {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+    const GENERATOR = Generator { generate, size: 8 };
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
     input
         .split(' ')
-        .map(|num| num.trim().parse::<u64>().unwrap())
+        .map(|num| aoc_core::parse::number(input, num.trim()))
         .collect()
 }
 
+/// A line of `size` stones, engraved with up to 7 digits.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<String> = (0..size)
+        .map(|_| rng.range(0..10_000_000u64).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
 fn even_digits(n: u64) -> Option<(u64, u64)> {
     match n {
         10..100 => Some((n / 10, n % 10)),
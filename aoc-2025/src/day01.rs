@@ -1,10 +1,18 @@
-#[derive(Debug, Copy, Clone)]
+use aoc_core::parse::ParseError;
+
+aoc_core::solution! {
+    type Input = Vec<Op>;
+    type Part1 = u32;
+    type Part2 = u32;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Dir {
     Left,
     Right,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Op {
     dir: Dir,
     step: i32,
@@ -25,17 +33,16 @@ impl Op {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Op> {
+pub fn parse_input(input: &str) -> Result<Vec<Op>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let (dir, step) = line.split_at(1);
-            let step_size = step.parse::<u32>().unwrap();
-            match dir {
-                "L" => Op::new(Dir::Left, step_size),
-                "R" => Op::new(Dir::Right, step_size),
-                _ => unreachable!(),
-            }
+            let dir = match line.get(..1) {
+                Some("L") => Dir::Left,
+                Some("R") => Dir::Right,
+                _ => return Err(ParseError::new(input, line, "L or R")),
+            };
+            Ok(Op::new(dir, aoc_core::parse::number(input, &line[1..])?))
         })
         .collect()
 }
@@ -66,3 +73,33 @@ pub fn part2(ops: &[Op]) -> u32 {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn rotations() -> impl Strategy<Value = Vec<Op>> {
+        let dir = prop::sample::select(&[Dir::Left, Dir::Right][..]);
+        prop::collection::vec(
+            (dir, 0..1000u32).prop_map(|(dir, step_size)| Op::new(dir, step_size)),
+            1..30,
+        )
+    }
+
+    fn render(rotations: &[Op]) -> String {
+        rotations
+            .iter()
+            .map(|op| match op.dir {
+                Dir::Left => format!("L{}\n", op.step_size),
+                Dir::Right => format!("R{}\n", op.step_size),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(rotations, |rotations| render(rotations), parse_input);
+    }
+}
@@ -1,6 +1,13 @@
+use aoc_core::parse::ParseError;
 use std::ops::Index;
 
-#[derive(Debug, Copy, Clone)]
+aoc_core::solution! {
+    type Input = Vec<Range>;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Range {
     start: u64,
     end: u64,
@@ -42,15 +49,17 @@ impl Index<usize> for Buffer {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Range> {
+pub fn parse_input(input: &str) -> Result<Vec<Range>, ParseError> {
     input
         .trim()
         .split(',')
         .map(|line| {
-            let (start, end) = line.split_once('-').unwrap();
-            let start = start.parse::<u64>().unwrap();
-            let end = end.parse::<u64>().unwrap();
-            Range { start, end }
+            let (start, end) = line
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(input, line, "range"))?;
+            let start = aoc_core::parse::number(input, start)?;
+            let end = aoc_core::parse::number(input, end)?;
+            Ok(Range { start, end })
         })
         .collect()
 }
@@ -92,3 +101,33 @@ pub fn part2(ranges: &[Range]) -> u64 {
         .filter(|&id| sequence_repeated(id, &mut buf))
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::fuzz;
+    use proptest::prelude::*;
+
+    fn ranges() -> impl Strategy<Value = Vec<Range>> {
+        prop::collection::vec(
+            (1..1u64 << 40, 0..1u64 << 20).prop_map(|(start, len)| Range {
+                start,
+                end: start + len,
+            }),
+            1..20,
+        )
+    }
+
+    fn render(ranges: &[Range]) -> String {
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|range| format!("{}-{}", range.start, range.end))
+            .collect();
+        ranges.join(",") + "\n"
+    }
+
+    #[test]
+    fn round_trips() {
+        fuzz::round_trips(ranges, |ranges| render(ranges), parse_input);
+    }
+}
@@ -1,7 +1,18 @@
-pub fn parse_input(input: &str) -> Vec<Vec<u8>> {
+use aoc_core::parse::ParseError;
+
+aoc_core::solution! {
+    type Input = Vec<Vec<u8>>;
+    type Part1 = u64;
+    type Part2 = u64;
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
     input
         .lines()
-        .map(|line| line.bytes().map(|byte| byte - b'0').collect())
+        .map(|line| match line.find(|c: char| !c.is_ascii_digit()) {
+            Some(idx) => Err(ParseError::new(input, &line[idx..], "digit")),
+            None => Ok(line.bytes().map(|byte| byte - b'0').collect()),
+        })
         .collect()
 }
 
@@ -1,36 +1,31 @@
-use std::time::{Duration, Instant};
+use aoc_core::{Year, days};
+
+aoc_core::counting_allocator!();
 
 mod day01;
 mod day02;
 mod day03;
 
-fn time<F, R>(f: F) -> (Duration, R)
-where
-    F: Fn() -> R,
-{
-    let now = Instant::now();
-    let result = f();
-    (now.elapsed(), result)
-}
-
-macro_rules! day {
-    ($mod:ident) => {
-        let input = include_str!(concat!("../data/", stringify!($mod), ".txt"));
-        let (dur, input) = time(|| $mod::parse_input(&input));
-        println!(":: {}", stringify!($mod));
-        println!("generator: {:?}", dur);
-        println!("part1: {:?}", time(|| $mod::part1(&input)));
-        println!("part2: {:?}", time(|| $mod::part2(&input)));
-        println!();
-    };
+fn year() -> Year {
+    Year {
+        year: 2025,
+        days: days![day01, day02, day03,],
+    }
 }
 
 fn main() {
-    let now = Instant::now();
+    aoc_core::run(year());
+}
 
-    day!(day01);
-    day!(day02);
-    day!(day03);
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
 
-    println!("total: {:?}", now.elapsed());
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generated_inputs_parse() {
+        aoc_core::generate::check(&super::year());
+    }
 }
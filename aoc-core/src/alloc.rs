@@ -0,0 +1,226 @@
+//! Counting what the solutions allocate, next to how long they take.
+//!
+//! Each year's runner installs [`Counting`] as its global allocator with
+//! [`counting_allocator!`](crate::counting_allocator), leaving anything
+//! else linking aoc-core, like the build scripts, on the system one. It
+//! hands out memory from the system allocator, keeping count of it for
+//! the thread asking, and [`measure`] reads those counts around a step.
+//! Counting per thread keeps the days solved at once with `--parallel`
+//! out of each other's numbers.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The system allocator, counting allocations by thread.
+pub struct Counting;
+
+/// A thread's running counts. Memory freed by another thread than the
+/// one that allocated it can take `live` below zero.
+struct Counters {
+    live: Cell<isize>,
+    peak: Cell<isize>,
+    allocated: Cell<usize>,
+    allocations: Cell<usize>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            live: Cell::new(0),
+            peak: Cell::new(0),
+            allocated: Cell::new(0),
+            allocations: Cell::new(0),
+        }
+    };
+}
+
+/// Whether [`Counting`] has seen an allocation, meaning it's the global
+/// allocator.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Count `size` bytes allocated, or freed if negative, in a new block
+/// if `new`. Threads being torn down don't count anymore.
+fn count(size: isize, new: bool) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    let _ = COUNTERS.try_with(|counters| {
+        let live = counters.live.get() + size;
+        counters.live.set(live);
+        counters.peak.set(counters.peak.get().max(live));
+        if new {
+            counters
+                .allocated
+                .set(counters.allocated.get() + size as usize);
+            counters.allocations.set(counters.allocations.get() + 1);
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size() as isize, true);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size() as isize, true);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(-(layout.size() as isize), false);
+    }
+
+    /// Growing or shrinking a block counts as allocating a new one of
+    /// the new size and freeing the old one, which is what it costs when
+    /// it can't be done in place.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            count(-(layout.size() as isize), false);
+            count(new_size as isize, true);
+        }
+        new
+    }
+}
+
+/// What a step allocated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// The most it held at once, in bytes, over what was live before.
+    pub peak: usize,
+    /// Everything it allocated, in bytes, whether freed since or not.
+    pub allocated: usize,
+    pub allocations: usize,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.allocations {
+            0 => write!(f, "no allocations"),
+            1 => write!(f, "peak {}, 1 allocation", Bytes(self.peak)),
+            allocations => write!(
+                f,
+                "peak {}, {} in {} allocations",
+                Bytes(self.peak),
+                Bytes(self.allocated),
+                allocations
+            ),
+        }
+    }
+}
+
+/// A size, in the largest binary unit it has one of.
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1 << 10 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Run `f`, counting what it allocates on this thread, or `None` if
+/// [`Counting`] isn't the global allocator.
+pub fn measure<F, R>(f: F) -> (Option<Usage>, R)
+where
+    F: FnOnce() -> R,
+{
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return (None, f());
+    }
+
+    let (live, peak, allocated, allocations) = COUNTERS.with(|counters| {
+        let before = (
+            counters.live.get(),
+            counters.peak.get(),
+            counters.allocated.get(),
+            counters.allocations.get(),
+        );
+        counters.peak.set(counters.live.get());
+        before
+    });
+
+    let result = f();
+
+    let usage = COUNTERS.with(|counters| {
+        let usage = Usage {
+            peak: (counters.peak.get() - live).max(0) as usize,
+            allocated: counters.allocated.get() - allocated,
+            allocations: counters.allocations.get() - allocations,
+        };
+        // Measurements can nest, the outer one's peak is the highest of
+        // both.
+        counters.peak.set(counters.peak.get().max(peak));
+        usage
+    });
+    (Some(usage), result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    #[test]
+    fn counts_allocations() {
+        let (usage, ()) = measure(|| {
+            let small = black_box(vec![0u8; 1000]);
+            let large = black_box(vec![0u64; 1000]);
+            drop(large);
+            drop(small);
+            black_box(Vec::<u8>::with_capacity(100));
+        });
+        assert_eq!(
+            usage,
+            Some(Usage {
+                peak: 9000,
+                allocated: 9100,
+                allocations: 3,
+            })
+        );
+
+        let (outer, ()) = measure(|| {
+            let (inner, table) = measure(|| black_box(vec![0u32; 256]));
+            assert_eq!(inner.unwrap().peak, 1024);
+            drop(table);
+            black_box(vec![0u8; 10]);
+        });
+        let outer = outer.unwrap();
+        assert_eq!(outer.peak, 1024);
+        assert_eq!(outer.allocations, 2);
+        assert_eq!(measure(|| 1 + 1).0, Some(Usage::default()));
+    }
+
+    #[test]
+    fn can_describe_usage() {
+        let usage = Usage {
+            peak: 1536,
+            allocated: 3 << 20,
+            allocations: 12,
+        };
+        assert_eq!(usage.to_string(), "peak 1.5 KiB, 3.0 MiB in 12 allocations");
+        assert_eq!(Usage::default().to_string(), "no allocations");
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+    }
+}
@@ -0,0 +1,376 @@
+//! Expected answers, loaded from a small TOML file kept next to the
+//! inputs. `answers.toml` holds the answers known to be right, and
+//! `recorded.toml`, in the same format, the ones only recorded from a
+//! solver, which can tell when an answer changes but not that it's right:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 1564
+//! part2 = "CMZ"
+//!
+//! [day10]
+//! part2 = """
+//! #..#.
+//! ####.
+//! """
+//! ```
+use crate::{
+    parse::{self, tag, IResult, ParseError},
+    tables,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{escaped_transform, is_not, take_until},
+    character::complete::{char, digit1, one_of},
+    combinator::{consumed, map, opt, recognize, value},
+    multi::many0,
+    sequence::{delimited, pair},
+};
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+/// An expected answer, as written in the answers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Number(String),
+    Text(String),
+}
+
+impl Expected {
+    /// How an answer given as text is written down: as a number if it
+    /// looks like one.
+    pub fn new(answer: &str) -> Self {
+        let digits = answer.strip_prefix('-').unwrap_or(answer);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            Expected::Number(answer.to_string())
+        } else {
+            Expected::Text(answer.to_string())
+        }
+    }
+
+    /// Check a rendered answer. Trailing newlines are ignored so that
+    /// multi-line answers don't have to agree on how they end.
+    pub fn matches(&self, answer: &str) -> bool {
+        let answer = answer.trim_end_matches('\n');
+        match self {
+            Expected::Number(number) => answer == number,
+            Expected::Text(text) => answer == text.trim_end_matches('\n'),
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Number(number) => write!(f, "{}", number),
+            Expected::Text(text) if text.contains('\n') => {
+                write!(f, "\n{}", text.trim_end_matches('\n'))
+            }
+            Expected::Text(text) => write!(f, "{:?}", text),
+        }
+    }
+}
+
+/// The outcome of checking an answer against the answers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(Expected),
+    /// The answer is the one recorded for the part, which nobody
+    /// confirmed.
+    Unverified,
+    /// The answer isn't the one recorded for the part anymore.
+    Changed(Expected),
+    Unknown,
+    /// The solution came up empty, for the given reason.
+    Missing(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unverified => write!(f, "UNVERIFIED"),
+            Verdict::Changed(recorded) => write!(f, "FAIL, recorded {}", recorded),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+            Verdict::Missing(reason) => write!(f, "FAIL, no answer: {}", reason),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+fn text(input: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        map(
+            opt(escaped_transform(
+                is_not("\\\"\n"),
+                '\\',
+                alt((
+                    value("\\", char('\\')),
+                    value("\"", char('"')),
+                    value("\n", char('n')),
+                )),
+            )),
+            Option::unwrap_or_default,
+        ),
+        char('"'),
+    )(input)
+}
+
+/// A multi-line string, kept verbatim apart from the newline directly
+/// after the opening quotes.
+fn multiline_text(input: &str) -> IResult<&str, String> {
+    map(
+        delimited(
+            pair(tag("\"\"\""), opt(char('\n'))),
+            take_until("\"\"\""),
+            tag("\"\"\""),
+        ),
+        str::to_string,
+    )(input)
+}
+
+fn expected(input: &str) -> IResult<&str, Expected> {
+    alt((
+        map(multiline_text, Expected::Text),
+        map(
+            recognize(pair(opt(one_of("+-")), digit1)),
+            |number: &str| Expected::Number(number.trim_start_matches('+').to_string()),
+        ),
+        map(text, Expected::Text),
+    ))(input)
+}
+
+/// A line of an answers file, its entries being parts' answers.
+type Line = tables::Line<(u8, Expected)>;
+
+fn answer(input: &str) -> IResult<&str, (u8, Expected)> {
+    tables::entry(tables::part, expected)(input)
+}
+
+/// The expected answers for every day of a year.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, u8), Expected>);
+
+impl Answers {
+    /// Load answers from a file. A missing file just means nothing is
+    /// known yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().map_err(Error::Parse),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(Error::Io(err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Expected> {
+        self.0.get(&(day, part))
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, expected: Expected) {
+        self.0.insert((day, part), expected);
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected.matches(answer) => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Check an answer against recorded answers, which it can match but
+    /// never pass.
+    pub fn check_recorded(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(recorded) if recorded.matches(answer) => Verdict::Unverified,
+            Some(recorded) => Verdict::Changed(recorded.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// A file's content ending in a newline, which its last line needs.
+fn terminated(content: &str) -> String {
+    let mut content = content.to_string();
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+/// Split a newline-terminated file into its lines, along with their text.
+fn lines(content: &str) -> Result<Vec<(&str, Line)>, ParseError> {
+    parse::all(content, many0(consumed(tables::line(answer))))
+}
+
+/// Parse the answers to a single example, which are the same `partN = ...`
+/// lines as in the answers file but without any table.
+pub fn parse_parts(content: &str) -> Result<BTreeMap<u8, Expected>, ParseError> {
+    let content = terminated(content);
+    let mut parts = BTreeMap::new();
+    for (text, line) in lines(&content)? {
+        match line {
+            Line::Table(_) => return Err(ParseError::new(&content, text, "partN entry")),
+            Line::Entry((part, expected)) => {
+                parts.insert(part, expected);
+            }
+            Line::Blank => {}
+        }
+    }
+    Ok(parts)
+}
+
+impl std::str::FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let content = terminated(content);
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+        for (text, line) in lines(&content)? {
+            match line {
+                Line::Table(table) => day = Some(table),
+                Line::Entry((part, expected)) => {
+                    let day =
+                        day.ok_or_else(|| ParseError::new(&content, text, "[dayNN] table"))?;
+                    answers.insert((day, part), expected);
+                }
+                Line::Blank => {}
+            }
+        }
+
+        Ok(Self(answers))
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut day = None;
+        for (&(table, part), expected) in &self.0 {
+            if day != Some(table) {
+                if day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{:02}]", table)?;
+                day = Some(table);
+            }
+
+            write!(f, "part{} = ", part)?;
+            match expected {
+                Expected::Number(number) => writeln!(f, "{}", number)?,
+                Expected::Text(text) if text.contains('\n') => {
+                    writeln!(f, "\"\"\"\n{}\n\"\"\"", text.trim_end_matches('\n'))?
+                }
+                Expected::Text(text) => {
+                    writeln!(f, "\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_answers() {
+        let answers: Answers = "\
+# comment
+[day01]
+part1 = 1564
+part2 = -12 # trailing
+
+[day05]
+part1 = \"CMZ\"
+part2=\"a \\\"b\\\"\""
+            .parse()
+            .unwrap();
+
+        assert_eq!(answers.check(1, 1, "1564"), Verdict::Pass);
+        assert_eq!(answers.check(1, 2, "-12"), Verdict::Pass);
+        assert_eq!(answers.check(5, 1, "CMZ"), Verdict::Pass);
+        assert_eq!(answers.check(5, 2, "a \"b\""), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, 1, "1565"),
+            Verdict::Fail(Expected::Number("1564".to_string()))
+        );
+        assert_eq!(answers.check(2, 1, "1"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn recorded_answers_never_pass() {
+        let recorded: Answers = "[day03]\npart1 = 176\n".parse().unwrap();
+        assert_eq!(recorded.check_recorded(3, 1, "176"), Verdict::Unverified);
+        assert_eq!(
+            recorded.check_recorded(3, 1, "177"),
+            Verdict::Changed(Expected::Number("176".to_string()))
+        );
+        assert_eq!(recorded.check_recorded(3, 2, "1"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn can_parse_multiline_answers() {
+        let answers: Answers =
+            "[day10]\npart2 = \"\"\"\n#..#\n####\n\"\"\"\n\n[day11]\npart1 = 3\n"
+                .parse()
+                .unwrap();
+
+        assert_eq!(answers.check(10, 2, "#..#\n####"), Verdict::Pass);
+        assert_eq!(answers.check(10, 2, "#..#\n####\n"), Verdict::Pass);
+        assert_eq!(answers.check(11, 1, "3"), Verdict::Pass);
+    }
+
+    #[test]
+    fn can_parse_example_parts() {
+        let parts = parse_parts("part1 = 7\n\npart2 = \"CMZ\"").unwrap();
+        assert_eq!(parts[&1], Expected::Number("7".to_string()));
+        assert_eq!(parts[&2], Expected::Text("CMZ".to_string()));
+
+        let err = parse_parts("part1 = 7\n[day01]\n").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "partN entry"));
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers: Answers = "[day01]\npart1 = -12\n".parse().unwrap();
+        answers.record(1, 2, Expected::new("a \"b\" \\"));
+        answers.record(10, 2, Expected::new("#..#\n####\n"));
+        answers.record(5, 1, Expected::new("42"));
+        assert_eq!(answers.get(5, 1), Some(&Expected::Number("42".to_string())));
+
+        let content = answers.to_string();
+        assert!(content.starts_with("[day01]\npart1 = -12\npart2 = "));
+        assert!(content.contains("\n\n[day05]\npart1 = 42\n\n[day10]\n"));
+        assert_eq!(content.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let err = "[day01]\npart1 = 12\npart2 = oops\n"
+            .parse::<Answers>()
+            .unwrap_err();
+        assert_eq!((err.line, err.snippet.as_str()), (3, "part2 = oops"));
+    }
+}
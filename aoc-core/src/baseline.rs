@@ -0,0 +1,240 @@
+//! Per-step run times saved from an earlier run, to catch performance
+//! regressions. They're kept next to the inputs in the same small TOML
+//! subset as the answers, in nanoseconds:
+//!
+//! ```toml
+//! [day01]
+//! parse = 47168
+//! part1 = 383
+//! part2 = 220
+//! ```
+use crate::{
+    parse::{self, tag, IResult, ParseError},
+    tables::{self, Line},
+};
+use nom::{
+    branch::alt,
+    character::complete::digit1,
+    combinator::{consumed, map, map_res, value},
+    multi::many0,
+};
+use std::{collections::BTreeMap, fmt, fs, io, path::Path, time::Duration};
+
+/// Slowdowns smaller than this are put down to noise, whatever the
+/// threshold says.
+const NOISE: Duration = Duration::from_micros(1);
+
+/// A timed step of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// A step that got slower than its baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u8,
+    pub step: Step,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Regression {
+    /// How much slower the step got, in percent.
+    pub fn slowdown(&self) -> f64 {
+        (self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day{:02} {}: {:?} -> {:?} (+{:.1}%)",
+            self.day,
+            self.step,
+            self.before,
+            self.after,
+            self.slowdown()
+        )
+    }
+}
+
+fn step(input: &str) -> IResult<&str, Step> {
+    alt((
+        value(Step::Parse, tag("parse")),
+        map(tables::part, Step::Part),
+    ))(input)
+}
+
+fn timing(input: &str) -> IResult<&str, (Step, Duration)> {
+    tables::entry(
+        step,
+        map_res(digit1, |nanos: &str| {
+            nanos.parse().map(Duration::from_nanos)
+        }),
+    )(input)
+}
+
+/// The baseline run times for every day of a year.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u8, Step), Duration>);
+
+impl Baseline {
+    /// Load a baseline from a file. A missing file means there's nothing
+    /// to compare against yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse().map_err(Error::Parse),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(Error::Io(err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, step: Step) -> Option<Duration> {
+        self.0.get(&(day, step)).copied()
+    }
+
+    pub fn record(&mut self, day: u8, step: Step, elapsed: Duration) {
+        self.0.insert((day, step), elapsed);
+    }
+
+    /// Check a step's run time against its baseline, `threshold` being
+    /// the slowdown tolerated in percent.
+    pub fn compare(
+        &self,
+        day: u8,
+        step: Step,
+        elapsed: Duration,
+        threshold: u32,
+    ) -> Option<Regression> {
+        let before = self.get(day, step)?;
+        let regression = Regression {
+            day,
+            step,
+            before,
+            after: elapsed,
+        };
+        (elapsed > before + NOISE && regression.slowdown() > threshold as f64).then_some(regression)
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Run times in nanoseconds, written by --save-baseline.")?;
+        let mut day = None;
+        for (&(table, step), elapsed) in &self.0 {
+            if day != Some(table) {
+                writeln!(f, "\n[day{:02}]", table)?;
+                day = Some(table);
+            }
+            writeln!(f, "{} = {}", step, elapsed.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Baseline {
+    type Err = ParseError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut content = content.to_string();
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        let content = content.as_str();
+
+        let lines = parse::all(content, many0(consumed(tables::line(timing))))?;
+
+        let mut baseline = Self::default();
+        let mut day = None;
+        for (text, line) in lines {
+            match line {
+                Line::Table(table) => day = Some(table),
+                Line::Entry((step, elapsed)) => {
+                    let day = day.ok_or_else(|| ParseError::new(content, text, "[dayNN] table"))?;
+                    baseline.record(day, step, elapsed);
+                }
+                Line::Blank => {}
+            }
+        }
+
+        Ok(baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(1, Step::Parse, Duration::from_micros(47));
+        baseline.record(1, Step::Part(2), Duration::from_nanos(220));
+        baseline.record(14, Step::Part(1), Duration::from_millis(3));
+
+        assert_eq!(baseline.to_string().parse(), Ok(baseline));
+    }
+
+    #[test]
+    fn flags_slowdowns_over_the_threshold() {
+        let baseline: Baseline = "[day05]\npart1 = 10000 # 10µs\npart2 = 200\n"
+            .parse()
+            .unwrap();
+
+        let slower = baseline.compare(5, Step::Part(1), Duration::from_micros(13), 20);
+        assert_eq!(
+            slower.map(|regression| regression.slowdown().round()),
+            Some(30.0)
+        );
+        assert_eq!(
+            baseline.compare(5, Step::Part(1), Duration::from_micros(11), 20),
+            None
+        );
+        // Doubling 200ns is still well within the noise.
+        assert_eq!(
+            baseline.compare(5, Step::Part(2), Duration::from_nanos(400), 20),
+            None
+        );
+        assert_eq!(
+            baseline.compare(6, Step::Parse, Duration::from_secs(1), 20),
+            None
+        );
+    }
+
+    #[test]
+    fn needs_a_table() {
+        let err = "part1 = 10\n".parse::<Baseline>().unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (1, "[dayNN] table"));
+    }
+}
@@ -0,0 +1,112 @@
+//! Repeated timing of a single step, for the `--bench` mode.
+//!
+//! The step is first warmed up, which also tells roughly how long a run
+//! takes. Fast steps are then timed in batches, so that each sample is
+//! long enough for the clock to resolve it, and the per-run times are
+//! summarised as min/median/p95.
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// How long to run a step before measuring it.
+const WARMUP: Duration = Duration::from_millis(100);
+/// How long to keep collecting samples, once `MIN_SAMPLES` are in.
+const BUDGET: Duration = Duration::from_secs(1);
+/// The shortest a single sample should take.
+const SAMPLE: Duration = Duration::from_micros(10);
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 10_000;
+
+/// Timings of a step over many runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    /// Size of the puzzle input, to report throughput against.
+    pub bytes: usize,
+}
+
+impl Stats {
+    /// Summarise per-run samples, in any order.
+    pub fn from_samples(mut samples: Vec<Duration>, runs: usize, bytes: usize) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort_unstable();
+
+        let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+        Self {
+            runs,
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+            bytes,
+        }
+    }
+
+    /// Input processed per second, at the median run time.
+    pub fn throughput(&self) -> f64 {
+        self.bytes as f64 / self.median.as_secs_f64()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, p95 {:?}, {:.1} MiB/s, {} runs",
+            self.min,
+            self.median,
+            self.p95,
+            self.throughput() / (1 << 20) as f64,
+            self.runs
+        )
+    }
+}
+
+/// Time `f` repeatedly, `bytes` being the size of the input it works on.
+pub fn measure<F, R>(bytes: usize, mut f: F) -> Stats
+where
+    F: FnMut() -> R,
+{
+    let start = Instant::now();
+    let mut warmup_runs = 0u32;
+    while warmup_runs == 0 || start.elapsed() < WARMUP {
+        black_box(f());
+        warmup_runs += 1;
+    }
+
+    let per_run = start.elapsed() / warmup_runs;
+    let batch = (SAMPLE.as_nanos() / per_run.as_nanos().max(1)).max(1) as u32;
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < MIN_SAMPLES || (samples.len() < MAX_SAMPLES && start.elapsed() < BUDGET) {
+        let now = Instant::now();
+        for _ in 0..batch {
+            black_box(f());
+        }
+        samples.push(now.elapsed() / batch);
+    }
+
+    let runs = samples.len() * batch as usize;
+    Stats::from_samples(samples, runs, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_summarise_samples() {
+        let samples = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = Stats::from_samples(samples, 100, 1 << 20);
+
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(50));
+        assert_eq!(stats.p95, Duration::from_micros(95));
+        assert_eq!(stats.throughput().round(), 20_971_520_000.0);
+    }
+}
@@ -0,0 +1,547 @@
+use crate::output::Format;
+use std::{fmt, ops::RangeInclusive, path::PathBuf};
+
+const USAGE: &str = "\
+usage: [options]
+       new-day [--year YEAR] --day DAY
+       submit [--year YEAR] --day DAY --part PART [--answer ANSWER]
+
+commands:
+    new-day             add a day to the year: its module, registered with the
+                        runner, and empty example and input files
+    submit              submit the part's answer, or the given one, unless
+                        earlier attempts ruled it out, with the session in
+                        AOC_SESSION; a right answer goes in the answers file
+
+options:
+    -y, --year YEAR     only run the given year
+    -d, --day DAYS      only run the given days, e.g. 14, 1-5 or 1,3,7-9
+    -p, --part PART     only run part 1 or part 2
+    -i, --inputs DIR    read inputs and answers from DIR instead
+    --input FILE        read the input of the single selected day from FILE
+    --fetch-from DIR    copy missing inputs from DIR rather than downloading
+                        them with the session in AOC_SESSION
+    -b, --bench         time each step over many runs
+    --variants          also run every part's variants, checking they agree
+    --parallel          solve the days all at once, on as many threads as
+                        there are cores
+    --save-baseline     save the run times as the baseline to compare against
+    --compare           fail on slowdowns against the baseline or going over
+                        the yearly time budget
+    --threshold PCT     slowdown tolerated by --compare, in percent (default 20)
+    --format FORMAT     print results as text (default), json or csv
+    -g, --generate SIZE solve generated inputs of the given size instead,
+                        e.g. the side of a grid or the number of lines
+    --seed SEED         seed for --generate (default 0)
+    --emit              print the generated input instead of solving it
+    --answer ANSWER     the answer to submit, rather than the part's
+    -h, --help          show this message";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Help,
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+    UnknownArgument(String),
+    NeedsOneDay(&'static str),
+    Requires(&'static str, &'static str),
+    Conflict(&'static str, &'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Help => write!(f, "{}", USAGE),
+            Error::MissingValue(flag) => write!(f, "missing value for {}\n\n{}", flag, USAGE),
+            Error::InvalidValue(flag, value) => {
+                write!(f, "invalid value for {}: {:?}\n\n{}", flag, value, USAGE)
+            }
+            Error::UnknownArgument(arg) => write!(f, "unknown argument {:?}\n\n{}", arg, USAGE),
+            Error::NeedsOneDay(flag) => {
+                write!(
+                    f,
+                    "{} needs a single day picked with --day\n\n{}",
+                    flag, USAGE
+                )
+            }
+            Error::Requires(flag, other) => write!(f, "{} needs {}\n\n{}", flag, other, USAGE),
+            Error::Conflict(flag, other) => {
+                write!(f, "{} can't be used with {}\n\n{}", flag, other, USAGE)
+            }
+        }
+    }
+}
+
+/// A set of puzzle days, stored as a bitmask since days only run 1 to 25.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Days(u32);
+
+impl Days {
+    const DAYS: RangeInclusive<u8> = 1..=25;
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.0 & 1 << day != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        Self::DAYS.filter(move |&day| self.contains(day))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn parse(spec: &str) -> Option<Self> {
+        spec.split(',').try_fold(Self(0), |Days(mask), item| {
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let day = item.parse().ok()?;
+                    (day, day)
+                }
+            };
+
+            if start > end || !Self::DAYS.contains(&start) || !Self::DAYS.contains(&end) {
+                return None;
+            }
+            Some(Days((start..=end).fold(mask, |mask, day| mask | 1 << day)))
+        })
+    }
+}
+
+/// What the command line asks for.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Solve puzzles.
+    Run(Options),
+    /// Scaffold a new day, defaulting to the runner's year.
+    NewDay { year: Option<u16>, day: u8 },
+    /// Submit the answer to a part, its solution's unless given.
+    Submit {
+        year: Option<u16>,
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+        inputs: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    pub fn parse<I>(args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter().peekable();
+        let command = args.next_if(|arg| arg == "new-day" || arg == "submit");
+
+        // The commands reuse the run options' parsing, but only take the
+        // few that make sense for them.
+        let options = Options::parse(args)?;
+        let Options {
+            year,
+            days,
+            part,
+            ref inputs,
+            ref answer,
+            ..
+        } = options;
+        let one_day = |command| match days {
+            Some(days) if days.len() == 1 => Ok(days.iter().next().unwrap()),
+            Some(_) => Err(Error::NeedsOneDay(command)),
+            None => Err(Error::Requires(command, "--day")),
+        };
+
+        match command.as_deref() {
+            None if answer.is_some() => Err(Error::Requires("--answer", "submit")),
+            None => Ok(Command::Run(options)),
+            Some("new-day") => {
+                let day = one_day("new-day")?;
+                let expected = Options {
+                    year,
+                    days,
+                    ..Options::default()
+                };
+                if options != expected {
+                    return Err(Error::Conflict(
+                        "new-day",
+                        "options other than --year and --day",
+                    ));
+                }
+                Ok(Command::NewDay { year, day })
+            }
+            Some(_) => {
+                let day = one_day("submit")?;
+                let part = part.ok_or(Error::Requires("submit", "--part"))?;
+                let expected = Options {
+                    year,
+                    days,
+                    part: Some(part),
+                    inputs: inputs.clone(),
+                    answer: answer.clone(),
+                    ..Options::default()
+                };
+                if options != expected {
+                    return Err(Error::Conflict(
+                        "submit",
+                        "options other than --year, --day, --part, --inputs and --answer",
+                    ));
+                }
+                Ok(Command::Submit {
+                    year,
+                    day,
+                    part,
+                    answer: options.answer,
+                    inputs: options.inputs,
+                })
+            }
+        }
+    }
+}
+
+/// Which puzzles to run, as selected on the command line. Anything left
+/// unset means everything.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub year: Option<u16>,
+    pub days: Option<Days>,
+    pub part: Option<u8>,
+    pub inputs: Option<PathBuf>,
+    pub input: Option<PathBuf>,
+    pub fetch_from: Option<PathBuf>,
+    pub bench: bool,
+    pub variants: bool,
+    pub parallel: bool,
+    pub save_baseline: bool,
+    pub compare: bool,
+    pub threshold: Option<u32>,
+    pub format: Format,
+    pub generate: Option<usize>,
+    pub seed: Option<u64>,
+    pub emit: bool,
+    pub answer: Option<String>,
+}
+
+impl Options {
+    pub fn runs_day(&self, day: u8) -> bool {
+        self.days.is_none_or(|days| days.contains(day))
+    }
+
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    /// The slowdown `--compare` tolerates, in percent.
+    pub fn threshold(&self) -> u32 {
+        self.threshold.unwrap_or(20)
+    }
+
+    /// The seed `--generate` uses.
+    pub fn seed(&self) -> u64 {
+        self.seed.unwrap_or(0)
+    }
+
+    pub fn parse<I>(args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+
+            let mut value = |name: &'static str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(Error::MissingValue(name))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => return Err(Error::Help),
+                "-i" | "--inputs" => options.inputs = Some(value("--inputs")?.into()),
+                "--input" => options.input = Some(value("--input")?.into()),
+                "--fetch-from" => options.fetch_from = Some(value("--fetch-from")?.into()),
+                "-b" | "--bench" => options.bench = true,
+                "--variants" => options.variants = true,
+                "--parallel" => options.parallel = true,
+                "--save-baseline" => options.save_baseline = true,
+                "--compare" => options.compare = true,
+                "--threshold" => {
+                    let threshold = value("--threshold")?;
+                    options.threshold = Some(
+                        threshold
+                            .parse()
+                            .map_err(|_| Error::InvalidValue("--threshold", threshold))?,
+                    );
+                }
+                "-y" | "--year" => {
+                    let year = value("--year")?;
+                    options.year = Some(
+                        year.parse()
+                            .map_err(|_| Error::InvalidValue("--year", year))?,
+                    );
+                }
+                "-d" | "--day" => {
+                    let days = value("--day")?;
+                    options.days =
+                        Some(Days::parse(&days).ok_or(Error::InvalidValue("--day", days))?);
+                }
+                "-p" | "--part" => {
+                    let part = value("--part")?;
+                    options.part = match part.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(Error::InvalidValue("--part", part)),
+                    };
+                }
+                "--format" => {
+                    let format = value("--format")?;
+                    options.format = match format.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        "csv" => Format::Csv,
+                        _ => return Err(Error::InvalidValue("--format", format)),
+                    };
+                }
+                "-g" | "--generate" => {
+                    let size = value("--generate")?;
+                    options.generate = Some(
+                        size.parse()
+                            .map_err(|_| Error::InvalidValue("--generate", size))?,
+                    );
+                }
+                "--seed" => {
+                    let seed = value("--seed")?;
+                    options.seed = Some(
+                        seed.parse()
+                            .map_err(|_| Error::InvalidValue("--seed", seed))?,
+                    );
+                }
+                "--emit" => options.emit = true,
+                "--answer" => options.answer = Some(value("--answer")?),
+                _ => return Err(Error::UnknownArgument(flag)),
+            }
+        }
+
+        let one_day = options.days.is_some_and(|days| days.len() == 1);
+        if options.input.is_some() && !one_day {
+            return Err(Error::NeedsOneDay("--input"));
+        }
+        if options.input.is_some() {
+            // The baseline is for the input directory's inputs, not this one.
+            for (set, flag) in [
+                (options.save_baseline, "--save-baseline"),
+                (options.compare, "--compare"),
+            ] {
+                if set {
+                    return Err(Error::Conflict("--input", flag));
+                }
+            }
+        }
+        if options.generate.is_some() {
+            // Generated inputs have neither answers nor baseline times.
+            for (set, flag) in [
+                (options.input.is_some(), "--input"),
+                (options.fetch_from.is_some(), "--fetch-from"),
+                (options.save_baseline, "--save-baseline"),
+                (options.compare, "--compare"),
+            ] {
+                if set {
+                    return Err(Error::Conflict("--generate", flag));
+                }
+            }
+        } else if options.seed.is_some() {
+            return Err(Error::Requires("--seed", "--generate"));
+        } else if options.emit {
+            return Err(Error::Requires("--emit", "--generate"));
+        }
+        if options.parallel {
+            // Days running side by side slow each other down.
+            for (set, flag) in [
+                (options.bench, "--bench"),
+                (options.save_baseline, "--save-baseline"),
+                (options.compare, "--compare"),
+            ] {
+                if set {
+                    return Err(Error::Conflict("--parallel", flag));
+                }
+            }
+        }
+        if options.emit && !one_day {
+            return Err(Error::NeedsOneDay("--emit"));
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, Error> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults_to_everything() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options.year, None);
+        assert!((1..=25).all(|day| options.runs_day(day)));
+        assert!(options.runs_part(1) && options.runs_part(2));
+    }
+
+    #[test]
+    fn can_parse_selection() {
+        let options =
+            parse(&["--year", "2023", "-d", "1-3,7,20-21", "--part=2", "--bench"]).unwrap();
+        assert_eq!(options.year, Some(2023));
+        assert!(options.bench && !options.variants);
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(
+            options.days.unwrap().iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 7, 20, 21]
+        );
+        assert!(!options.runs_part(1) && options.runs_part(2));
+    }
+
+    #[test]
+    fn can_parse_format() {
+        assert_eq!(parse(&["--format", "csv"]).unwrap().format, Format::Csv);
+        assert_eq!(parse(&["--format=json"]).unwrap().format, Format::Json);
+        assert_eq!(
+            parse(&["--format", "xml"]),
+            Err(Error::InvalidValue("--format", "xml".to_string()))
+        );
+    }
+
+    #[test]
+    fn input_needs_one_day() {
+        let options = parse(&["--input", "mine.txt", "-d", "9"]).unwrap();
+        assert_eq!(options.input, Some(PathBuf::from("mine.txt")));
+
+        assert_eq!(
+            parse(&["--input", "mine.txt"]),
+            Err(Error::NeedsOneDay("--input"))
+        );
+        assert_eq!(
+            parse(&["--input", "mine.txt", "-d", "1-2"]),
+            Err(Error::NeedsOneDay("--input"))
+        );
+        assert_eq!(
+            parse(&["--input", "mine.txt", "-d", "9", "--save-baseline"]),
+            Err(Error::Conflict("--input", "--save-baseline"))
+        );
+        assert_eq!(
+            parse(&["--input", "mine.txt", "-d", "9", "--compare"]),
+            Err(Error::Conflict("--input", "--compare"))
+        );
+    }
+
+    #[test]
+    fn can_parse_generate() {
+        let options = parse(&["-g", "1000", "--seed=3", "--emit", "-d", "15"]).unwrap();
+        assert_eq!(options.generate, Some(1000));
+        assert_eq!(options.seed(), 3);
+        assert!(options.emit);
+
+        assert_eq!(
+            parse(&["-g", "10", "--compare"]),
+            Err(Error::Conflict("--generate", "--compare"))
+        );
+        assert_eq!(
+            parse(&["--seed", "3"]),
+            Err(Error::Requires("--seed", "--generate"))
+        );
+        assert_eq!(
+            parse(&["-g", "10", "--emit"]),
+            Err(Error::NeedsOneDay("--emit"))
+        );
+    }
+
+    #[test]
+    fn can_parse_new_day() {
+        let command = |args: &[&str]| Command::parse(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(
+            command(&["new-day", "--year", "2024", "-d", "4"]),
+            Ok(Command::NewDay {
+                year: Some(2024),
+                day: 4
+            })
+        );
+        assert_eq!(
+            command(&["-d", "4"]),
+            Ok(Command::Run(parse(&["-d", "4"]).unwrap()))
+        );
+        assert_eq!(
+            command(&["new-day"]),
+            Err(Error::Requires("new-day", "--day"))
+        );
+        assert_eq!(
+            command(&["new-day", "-d", "4-5"]),
+            Err(Error::NeedsOneDay("new-day"))
+        );
+        assert_eq!(
+            command(&["new-day", "-d", "4", "--bench"]),
+            Err(Error::Conflict(
+                "new-day",
+                "options other than --year and --day"
+            ))
+        );
+    }
+
+    #[test]
+    fn can_parse_submit() {
+        let command = |args: &[&str]| Command::parse(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(
+            command(&["submit", "-d", "4", "-p", "2", "--answer", "CMZ"]),
+            Ok(Command::Submit {
+                year: None,
+                day: 4,
+                part: 2,
+                answer: Some("CMZ".to_string()),
+                inputs: None,
+            })
+        );
+        assert_eq!(
+            command(&["submit", "-d", "4"]),
+            Err(Error::Requires("submit", "--part"))
+        );
+        assert_eq!(
+            command(&["-d", "4", "--answer", "CMZ"]),
+            Err(Error::Requires("--answer", "submit"))
+        );
+    }
+
+    #[test]
+    fn parallel_runs_skip_the_baseline() {
+        assert!(parse(&["--parallel", "--variants"]).unwrap().parallel);
+        assert_eq!(
+            parse(&["--parallel", "--bench"]),
+            Err(Error::Conflict("--parallel", "--bench"))
+        );
+        assert_eq!(
+            parse(&["--parallel", "--compare"]),
+            Err(Error::Conflict("--parallel", "--compare"))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_days() {
+        for days in ["0", "26", "5-3", "1,,2", "x"] {
+            assert_eq!(
+                parse(&["--day", days]),
+                Err(Error::InvalidValue("--day", days.to_string()))
+            );
+        }
+    }
+}
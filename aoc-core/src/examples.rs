@@ -0,0 +1,147 @@
+//! The examples from the puzzle statements, run as tests.
+//!
+//! Each year keeps them under `examples/dayNN/`, every `NAME.txt` input
+//! having its expected answers in a `NAME.toml` sidecar:
+//!
+//! ```toml
+//! part1 = 7
+//! part2 = 5
+//! ```
+//!
+//! The year's build script calls [`generate`], which writes a `#[test]`
+//! for every answer of every example, plus one fuzzing the day's parser
+//! with near-valid edits of the example. `main.rs` pulls them in with:
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! mod examples {
+//!     include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+//! }
+//! ```
+use crate::{answers, Answer, Solution};
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Turn an example's file name into something usable in a test name.
+fn ident(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Write the tests for the current crate's examples to
+/// `$OUT_DIR/examples.rs`. Meant to be called from a build script.
+pub fn generate() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("examples");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut tests = String::new();
+    let days = if root.is_dir() {
+        sorted_entries(&root)
+    } else {
+        Vec::new()
+    };
+    for dir in days.iter().filter(|dir| dir.is_dir()) {
+        let day = dir.file_name().unwrap().to_str().unwrap();
+
+        for input in sorted_entries(dir) {
+            if input.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+
+            let sidecar = input.with_extension("toml");
+            let content = fs::read_to_string(&sidecar)
+                .unwrap_or_else(|err| panic!("failed to read {}: {}", sidecar.display(), err));
+            let parts = answers::parse_parts(&content)
+                .unwrap_or_else(|err| panic!("bad answers in {}: {}", sidecar.display(), err));
+
+            let name = ident(input.file_stem().unwrap().to_str().unwrap());
+            writeln!(
+                tests,
+                "#[test]\n\
+                 fn {day}_{name}_parse_never_panics() {{\n    \
+                     aoc_core::fuzz::parse_never_panics::<super::{day}::Solver>(include_str!({input:?}));\n\
+                 }}\n",
+                day = day,
+                name = name,
+                input = input.display().to_string(),
+            )
+            .unwrap();
+            for part in parts.keys() {
+                writeln!(
+                    tests,
+                    "#[test]\n\
+                     fn {day}_{name}_part{part}() {{\n    \
+                         aoc_core::examples::check::<super::{day}::Solver>(\n        \
+                             include_str!({input:?}),\n        \
+                             include_str!({sidecar:?}),\n        \
+                             {part},\n    \
+                         );\n\
+                     }}\n",
+                    day = day,
+                    name = name,
+                    part = part,
+                    input = input.display().to_string(),
+                    sidecar = sidecar.display().to_string(),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(&out, tests)
+        .unwrap_or_else(|err| panic!("failed to write {}: {}", out.display(), err));
+}
+
+/// Solve one part of an example, and check it and its variants against
+/// the answers from its sidecar.
+pub fn check<S: Solution>(input: &str, answers: &str, part: u8) {
+    let parts = answers::parse_parts(answers).unwrap_or_else(|err| panic!("{}", err));
+    let expected = &parts[&part];
+
+    let input = S::parse(input).unwrap_or_else(|err| panic!("failed to parse example:\n{}", err));
+    let mut answers = Vec::new();
+    match part {
+        1 => {
+            answers.push(("part1", S::part1(&input).answer()));
+            for variant in S::part1_variants() {
+                answers.push((variant.name, (variant.solve)(&input).answer()));
+            }
+        }
+        2 => {
+            answers.push(("part2", S::part2(&input).answer()));
+            for variant in S::part2_variants() {
+                answers.push((variant.name, (variant.solve)(&input).answer()));
+            }
+        }
+        _ => panic!("there's no part{}", part),
+    }
+
+    for (name, answer) in answers {
+        let answer = answer.unwrap_or_else(|reason| panic!("{} found no answer: {}", name, reason));
+        assert!(
+            expected.matches(&answer),
+            "{} answered {:?}, expected {}",
+            name,
+            answer,
+            expected
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Strategies to fuzz puzzle input parsers with, behind the `fuzz`
+//! feature so only tests pull in proptest.
+//!
+//! Parsers get valid inputs, which should round-trip, and near-valid
+//! ones: a valid input with a few small edits, which should parse or
+//! fail cleanly but never panic.
+use crate::Solution;
+use proptest::{prelude::*, sample::Index};
+use std::fmt::Debug;
+
+/// A small edit to an input.
+#[derive(Debug, Clone)]
+enum Mutation {
+    Insert(Index, char),
+    Replace(Index, char),
+    Delete(Index),
+    Duplicate(Index, Index),
+    Truncate(Index),
+}
+
+/// Characters worth inserting: the punctuation puzzle inputs are made of,
+/// digits around number boundaries, and anything else now and then.
+fn noise() -> impl Strategy<Value = char> {
+    prop_oneof![
+        4 => prop::sample::select(&[' ', '\n', ',', '-', '#', '.', '[', ']', '(', ')', ':', '='][..]),
+        2 => proptest::char::range('0', '9'),
+        2 => proptest::char::range('A', 'Z'),
+        2 => proptest::char::range('a', 'z'),
+        1 => any::<char>(),
+    ]
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        (any::<Index>(), noise()).prop_map(|(at, c)| Mutation::Insert(at, c)),
+        (any::<Index>(), noise()).prop_map(|(at, c)| Mutation::Replace(at, c)),
+        any::<Index>().prop_map(Mutation::Delete),
+        (any::<Index>(), any::<Index>()).prop_map(|(from, len)| Mutation::Duplicate(from, len)),
+        any::<Index>().prop_map(Mutation::Truncate),
+    ]
+}
+
+impl Mutation {
+    fn apply(&self, input: &mut Vec<char>) {
+        let at = |index: &Index, len: usize| index.index(len + 1).min(len);
+        match self {
+            Mutation::Insert(index, c) => {
+                let at = at(index, input.len());
+                input.insert(at, *c);
+            }
+            Mutation::Replace(_, _) | Mutation::Delete(_) if input.is_empty() => {}
+            Mutation::Replace(index, c) => {
+                let at = index.index(input.len());
+                input[at] = *c;
+            }
+            Mutation::Delete(index) => {
+                let at = index.index(input.len());
+                input.remove(at);
+            }
+            Mutation::Duplicate(from, len) => {
+                let from = at(from, input.len());
+                let len = len.index(8).min(input.len() - from);
+                let copy: Vec<char> = input[from..from + len].to_vec();
+                input.splice(from..from, copy);
+            }
+            Mutation::Truncate(index) => {
+                let at = at(index, input.len());
+                input.truncate(at);
+            }
+        }
+    }
+}
+
+/// Valid inputs from `valid`, with one to a few small edits applied.
+pub fn near_valid<S>(valid: S) -> impl Strategy<Value = String>
+where
+    S: Strategy<Value = String>,
+{
+    (valid, prop::collection::vec(mutation(), 1..4)).prop_map(|(input, mutations)| {
+        let mut input: Vec<char> = input.chars().collect();
+        for mutation in &mutations {
+            mutation.apply(&mut input);
+        }
+        input.into_iter().collect()
+    })
+}
+
+/// Check that a day's parser doesn't panic on near-valid edits of
+/// `input`, typically one of its examples.
+pub fn parse_never_panics<S: Solution>(input: &'static str) {
+    proptest!(|(input in near_valid(Just(input.to_string())))| {
+        let _ = S::parse(&input);
+    });
+}
+
+/// Check that `parse` reads back every value `values()` makes, as written
+/// out by `render`, and doesn't panic on near-valid edits of what
+/// `render` writes.
+pub fn round_trips<T, E, V, S, R, P>(values: V, render: R, parse: P)
+where
+    T: PartialEq + Debug,
+    E: PartialEq + Debug,
+    V: Fn() -> S,
+    S: Strategy<Value = T>,
+    R: Fn(&T) -> String,
+    P: Fn(&str) -> Result<T, E>,
+{
+    proptest!(|(value in values())| {
+        prop_assert_eq!(parse(&render(&value)), Ok(value));
+    });
+    proptest!(|(input in near_valid(values().prop_map(|value| render(&value))))| {
+        let _ = parse(&input);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn near_valid_inputs_stay_near(input in near_valid(Just("1,2,3\n".to_string()))) {
+            // At most three edits, each adding at most 8 characters.
+            prop_assert!(input.chars().count() <= 6 + 3 * 8);
+        }
+    }
+}
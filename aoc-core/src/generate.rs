@@ -0,0 +1,158 @@
+//! Synthetic puzzle inputs, to see how solutions scale past the one
+//! input each of us gets.
+//!
+//! Days opt in from [`solution!`](crate::solution) with a
+//! [`Generator`], whose `size` is whatever scales the input: the side of
+//! a grid, the number of lines, stones, and so on.
+//!
+//! ```ignore
+//! aoc_core::solution! {
+//!     type Input = Vec<u8>;
+//!     type Part1 = u32;
+//!     type Part2 = u32;
+//!     const GENERATOR = Generator { generate, size: 100 };
+//! }
+//!
+//! pub fn generate(rng: &mut Rng, size: usize) -> String { ... }
+//! ```
+use crate::Year;
+use std::ops::Range;
+
+/// How to make up inputs for a day.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    /// Generate an input of the given size.
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// The size of an actual puzzle input.
+    pub size: usize,
+}
+
+/// A small, seedable random number generator (SplitMix64), so that a
+/// seed always gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, with a negligible bias for the bounds
+    /// inputs need.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "empty range");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    pub fn range<T: Uniform>(&mut self, range: Range<T>) -> T {
+        T::sample(self, range)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Integers [`Rng::range`] can pick from.
+pub trait Uniform: Sized {
+    fn sample(rng: &mut Rng, range: Range<Self>) -> Self;
+}
+
+macro_rules! uniform {
+    ($($int:ty),*) => {
+        $(impl Uniform for $int {
+            fn sample(rng: &mut Rng, range: Range<Self>) -> Self {
+                assert!(range.start < range.end, "empty range");
+                let span = range.end.abs_diff(range.start) as u64;
+                range.start.wrapping_add(rng.below(span) as $int)
+            }
+        })*
+    };
+}
+
+uniform!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// An input of `count` lines made by `line`.
+pub fn lines<F>(rng: &mut Rng, count: usize, mut line: F) -> String
+where
+    F: FnMut(&mut Rng) -> String,
+{
+    let mut out = String::new();
+    for _ in 0..count {
+        out.push_str(&line(rng));
+        out.push('\n');
+    }
+    out
+}
+
+/// A `width` by `height` grid of characters made by `cell`.
+pub fn grid<F>(rng: &mut Rng, width: usize, height: usize, mut cell: F) -> String
+where
+    F: FnMut(&mut Rng) -> char,
+{
+    lines(rng, height, |rng| (0..width).map(|_| cell(rng)).collect())
+}
+
+/// Solve a few generated inputs of every day that has a generator, at
+/// the size of a puzzle input, panicking if one doesn't parse or a
+/// variant disagrees with its part. Meant to be called from each year's
+/// tests.
+pub fn check(year: &Year) {
+    for day in &year.days {
+        let Some(generator) = day.generator else {
+            continue;
+        };
+        for seed in 0..3 {
+            let input = (generator.generate)(&mut Rng::new(seed), generator.size);
+            match day.cross_check(&input) {
+                Ok(mismatches) => {
+                    if let Some(mismatch) = mismatches.first() {
+                        panic!("{} with seed {}: {}", day.name, seed, mismatch);
+                    }
+                }
+                Err(err) => panic!(
+                    "{} generated an input that doesn't parse, with seed {}:\n{}",
+                    day.name, seed, err
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..5).contains(&rng.range(-3i32..5)));
+            assert!(rng.range(10u8..11) == 10);
+            assert!(rng.below(3) < 3);
+        }
+        assert_eq!(
+            (0..8).map(|_| Rng::new(1).next_u64()).collect::<Vec<_>>(),
+            vec![Rng::new(1).next_u64(); 8]
+        );
+    }
+}
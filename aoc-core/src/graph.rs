@@ -0,0 +1,476 @@
+//! Graphs of labelled nodes, for puzzles that describe one as a list of
+//! named connections.
+//!
+//! A [`Graph`] interns each label the first time it's seen and numbers
+//! its nodes from zero in that order, so everything past building it
+//! works on plain `usize` nodes, and can index tables of its own by
+//! them. Edges carry a weight, `()` when they don't need one, and go
+//! both ways in undirected graphs. Weighted graphs are also
+//! [`pathfinding::Graph`]s, to search for shortest paths.
+use crate::pathfinding;
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+#[derive(Debug, Clone)]
+pub struct Graph<L, W = ()> {
+    directed: bool,
+    labels: Vec<L>,
+    nodes: HashMap<L, usize>,
+    edges: Vec<Vec<(usize, W)>>,
+}
+
+impl<L, W> Graph<L, W> {
+    /// An empty graph whose edges go one way.
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            labels: Vec::new(),
+            nodes: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// An empty graph whose edges go both ways.
+    pub fn undirected() -> Self {
+        Self {
+            directed: false,
+            ..Self::directed()
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// How many nodes there are, numbered below that.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn label(&self, node: usize) -> &L {
+        &self.labels[node]
+    }
+
+    /// The edges out of `node`, to the node at the other end and with
+    /// their weight, in the order they were added.
+    pub fn edges(&self, node: usize) -> impl ExactSizeIterator<Item = (usize, &W)> {
+        self.edges[node]
+            .iter()
+            .map(|(next, weight)| (*next, weight))
+    }
+
+    pub fn neighbours(&self, node: usize) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.edges[node].iter().map(|&(next, _)| next)
+    }
+
+    /// The nodes reachable from `start`, depth first, `start` included.
+    pub fn dfs(&self, start: usize) -> Dfs<'_, L, W> {
+        Dfs {
+            graph: self,
+            stack: vec![start],
+            seen: vec![false; self.len()],
+        }
+    }
+
+    /// The nodes reachable from `start`, breadth first, along with how
+    /// many edges away they are.
+    pub fn bfs(&self, start: usize) -> Bfs<'_, L, W> {
+        let mut seen = vec![false; self.len()];
+        seen[start] = true;
+        Bfs {
+            graph: self,
+            queue: VecDeque::from([(start, 0)]),
+            seen,
+        }
+    }
+
+    /// Whether there's a path from `from` to `to`.
+    pub fn reachable(&self, from: usize, to: usize) -> bool {
+        self.dfs(from).any(|node| node == to)
+    }
+
+    /// The nodes ordered so that every edge goes forwards, or `None` if
+    /// there's a cycle in the way. Undirected edges are cycles.
+    pub fn toposort(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.len()];
+        for edges in &self.edges {
+            for &(next, _) in edges {
+                incoming[next] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<_> = (0..self.len())
+            .filter(|&node| incoming[node] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for next in self.neighbours(node) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// The groups of nodes that can all reach each other, or the
+    /// connected components of an undirected graph. A component comes
+    /// before any it has edges to.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // Kosaraju's: the nodes that finish last in a depth first walk
+        // head components, and walking the edges backwards from them
+        // doesn't leave their component for one not taken yet.
+        let mut finished = Vec::with_capacity(self.len());
+        let mut seen = vec![false; self.len()];
+        for root in 0..self.len() {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, edge)) = stack.pop() {
+                match self.edges[node].get(edge) {
+                    Some(&(next, _)) => {
+                        stack.push((node, edge + 1));
+                        if !seen[next] {
+                            seen[next] = true;
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => finished.push(node),
+                }
+            }
+        }
+
+        let mut backwards = vec![Vec::new(); self.len()];
+        for (node, edges) in self.edges.iter().enumerate() {
+            for &(next, _) in edges {
+                backwards[next].push(node);
+            }
+        }
+
+        let mut components = Vec::new();
+        let mut taken = vec![false; self.len()];
+        for &root in finished.iter().rev() {
+            if taken[root] {
+                continue;
+            }
+            taken[root] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for &previous in &backwards[node] {
+                    if !taken[previous] {
+                        taken[previous] = true;
+                        stack.push(previous);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Count the paths from `from` to `to` that go through each node it
+    /// says is `limited` at most once, apart from `revisits` of them that
+    /// may come up twice. Paths never come back to `from`, and stop at
+    /// `to`.
+    ///
+    /// A cycle that doesn't go through a limited node can be walked
+    /// forever, so this doesn't return for graphs where one can be
+    /// reached on the way.
+    pub fn count_paths<F>(&self, from: usize, to: usize, limited: F, revisits: usize) -> usize
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut walk = Walk {
+            graph: self,
+            from,
+            to,
+            limited,
+            visited: vec![false; self.len()],
+        };
+        walk.visited[from] = true;
+        walk.paths(from, revisits)
+    }
+}
+
+impl<L, W> Graph<L, W>
+where
+    L: Eq + Hash + Clone,
+{
+    /// The node labelled `label`, if there's one.
+    pub fn node<Q>(&self, label: &Q) -> Option<usize>
+    where
+        L: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.nodes.get(label).copied()
+    }
+
+    /// The node labelled `label`, adding it if it's new.
+    pub fn add_node(&mut self, label: L) -> usize {
+        if let Some(&node) = self.nodes.get(&label) {
+            return node;
+        }
+        let node = self.labels.len();
+        self.labels.push(label.clone());
+        self.nodes.insert(label, node);
+        self.edges.push(Vec::new());
+        node
+    }
+
+    /// Add an edge between the nodes labelled `from` and `to`, adding
+    /// them if they're new, and return their numbers.
+    pub fn add_edge(&mut self, from: L, to: L, weight: W) -> (usize, usize)
+    where
+        W: Clone,
+    {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        if !self.directed {
+            self.edges[to].push((from, weight.clone()));
+        }
+        self.edges[from].push((to, weight));
+        (from, to)
+    }
+
+    /// The same graph with its edges turned around.
+    pub fn reversed(&self) -> Self
+    where
+        W: Clone,
+    {
+        let mut edges = vec![Vec::new(); self.len()];
+        for (node, out) in self.edges.iter().enumerate() {
+            for (next, weight) in out {
+                edges[*next].push((node, weight.clone()));
+            }
+        }
+        Self {
+            directed: self.directed,
+            labels: self.labels.clone(),
+            nodes: self.nodes.clone(),
+            edges,
+        }
+    }
+}
+
+/// A depth first walk, see [`Graph::dfs`].
+pub struct Dfs<'a, L, W> {
+    graph: &'a Graph<L, W>,
+    stack: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl<L, W> Iterator for Dfs<'_, L, W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(node) = self.stack.pop() {
+            if std::mem::replace(&mut self.seen[node], true) {
+                continue;
+            }
+            // Backwards, to walk the first edge first.
+            let edges = self.graph.edges[node].iter().rev();
+            self.stack.extend(edges.map(|&(next, _)| next));
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// A breadth first walk, see [`Graph::bfs`].
+pub struct Bfs<'a, L, W> {
+    graph: &'a Graph<L, W>,
+    queue: VecDeque<(usize, usize)>,
+    seen: Vec<bool>,
+}
+
+impl<L, W> Iterator for Bfs<'_, L, W> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let (node, depth) = self.queue.pop_front()?;
+        for next in self.graph.neighbours(node) {
+            if !std::mem::replace(&mut self.seen[next], true) {
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((node, depth))
+    }
+}
+
+/// A path being counted, see [`Graph::count_paths`].
+struct Walk<'a, L, W, F> {
+    graph: &'a Graph<L, W>,
+    from: usize,
+    to: usize,
+    limited: F,
+    visited: Vec<bool>,
+}
+
+impl<L, W, F> Walk<'_, L, W, F>
+where
+    F: Fn(usize) -> bool,
+{
+    fn paths(&mut self, node: usize, revisits: usize) -> usize {
+        if node == self.to {
+            return 1;
+        }
+
+        let mut paths = 0;
+        for next in self.graph.neighbours(node) {
+            if next == self.from {
+                continue;
+            }
+            if !(self.limited)(next) {
+                paths += self.paths(next, revisits);
+            } else if !self.visited[next] {
+                self.visited[next] = true;
+                paths += self.paths(next, revisits);
+                self.visited[next] = false;
+            } else if revisits > 0 {
+                paths += self.paths(next, revisits - 1);
+            }
+        }
+        paths
+    }
+}
+
+impl<L, W> pathfinding::Graph for Graph<L, W>
+where
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    type Node = usize;
+    type Cost = W;
+
+    fn neighbours<F>(&self, node: usize, mut edge: F)
+    where
+        F: FnMut(usize, W),
+    {
+        for &(next, weight) in &self.edges[node] {
+            edge(next, weight);
+        }
+    }
+
+    fn node_count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn node_index(&self, node: usize) -> usize {
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The caves from 2021's day 12 example, small ones in lower case.
+    fn caves() -> Graph<&'static str> {
+        let mut graph = Graph::undirected();
+        for edge in ["start-A", "start-b", "A-c", "A-b", "b-d", "A-end", "b-end"] {
+            let (a, b) = edge.split_once('-').unwrap();
+            graph.add_edge(a, b, ());
+        }
+        graph
+    }
+
+    /// a → b → c ⇄ d, a → e → d, f on its own.
+    fn chain() -> Graph<char, u32> {
+        let mut graph = Graph::directed();
+        for (from, to, weight) in [
+            ('a', 'b', 1),
+            ('b', 'c', 1),
+            ('c', 'd', 1),
+            ('d', 'c', 1),
+            ('a', 'e', 5),
+            ('e', 'd', 1),
+        ] {
+            graph.add_edge(from, to, weight);
+        }
+        graph.add_node('f');
+        graph
+    }
+
+    #[test]
+    fn interns_labels() {
+        let graph = caves();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.node("start"), Some(0));
+        assert_eq!(graph.node("x"), None);
+        assert_eq!(graph.label(1), &"A");
+
+        let a = graph.node("A").unwrap();
+        let neighbours: Vec<_> = graph.neighbours(a).map(|node| *graph.label(node)).collect();
+        assert_eq!(neighbours, ["start", "c", "b", "end"]);
+    }
+
+    #[test]
+    fn walks_the_graph() {
+        let graph = chain();
+        let labels = |nodes: Vec<usize>| -> String {
+            nodes.into_iter().map(|node| graph.label(node)).collect()
+        };
+        assert_eq!(labels(graph.dfs(0).collect()), "abcde");
+        assert_eq!(
+            labels(graph.bfs(0).map(|(node, _)| node).collect()),
+            "abecd"
+        );
+        assert_eq!(graph.bfs(0).last(), Some((3, 2)));
+        assert!(graph.reachable(0, 3));
+        assert!(!graph.reachable(3, 0));
+        assert!(!graph.reachable(0, 5));
+        assert_eq!(labels(graph.reversed().dfs(3).collect()), "dcbae");
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let mut graph = chain();
+        assert_eq!(graph.toposort(), None);
+
+        graph = Graph::directed();
+        graph.add_edge('b', 'c', 1);
+        graph.add_edge('a', 'b', 1);
+        graph.add_edge('a', 'c', 1);
+        assert_eq!(graph.toposort(), Some(vec![2, 0, 1]));
+    }
+
+    #[test]
+    fn finds_components() {
+        let graph = chain();
+        let components = graph.strongly_connected_components();
+        assert_eq!(components, [vec![5], vec![0], vec![4], vec![1], vec![2, 3]]);
+
+        let components = caves().strongly_connected_components();
+        assert_eq!(components, [vec![0, 1, 2, 3, 4, 5]]);
+    }
+
+    #[test]
+    fn counts_paths() {
+        let graph = caves();
+        let (start, end) = (graph.node("start").unwrap(), graph.node("end").unwrap());
+        let small = |node| graph.label(node).chars().all(|c| c.is_ascii_lowercase());
+        assert_eq!(graph.count_paths(start, end, small, 0), 10);
+        assert_eq!(graph.count_paths(start, end, small, 1), 36);
+    }
+
+    #[test]
+    fn weighted_graphs_can_be_searched() {
+        let graph = chain();
+        let search = pathfinding::dijkstra(&graph, [0], |node| node == 3);
+        assert_eq!(search.target(), Some((3, 3)));
+        assert_eq!(search.target_path(), Some(vec![0, 1, 2, 3]));
+    }
+}
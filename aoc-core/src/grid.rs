@@ -0,0 +1,347 @@
+//! A dense, rectangular grid of cells, for the many puzzles set on a map.
+//!
+//! Cells are stored row by row, and addressed by `(x, y)` position with
+//! `x` going right and `y` going down, or by their index in that order.
+//! Positions are unsigned: step off the grid with `wrapping_add_signed`
+//! and the checked accessors turn the wrapped position down like any
+//! other out of bounds one.
+use crate::parse::{tag, Error, Expected, IResult};
+use nom::{bytes::complete::take_while1, error::ErrorKind, multi::separated_list1};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// The offsets to the four orthogonal neighbours: up, right, down, left.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets to all eight neighbours, clockwise from up.
+const ALL: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// The grid whose rows, `width` cells each, are laid out in `cells`.
+    ///
+    /// Panics unless `cells` holds a whole number of rows.
+    pub fn new(cells: Vec<T>, width: usize) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {}",
+            cells.len(),
+            width
+        );
+        let height = cells.len() / width;
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// A `width` by `height` grid filled with `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(vec![value; width * height], width)
+    }
+
+    /// A `width` by `height` grid with each cell set by `cell(x, y)`.
+    pub fn from_fn<F>(width: usize, height: usize, mut cell: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Self::new(cells, width)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All the cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The index of the cell at `(x, y)`, which must be on the grid.
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        assert!(self.contains(x, y), "({}, {}) is off the grid", x, y);
+        y * self.width + x
+    }
+
+    /// The position of the cell at `index`.
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        match self.contains(x, y) {
+            true => Some(&mut self.cells[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    /// Every position on the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + DoubleEndedIterator {
+        self.cells.chunks_exact(self.width)
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The positions `offsets` away from `(x, y)` that are on the grid.
+    fn around<'a>(
+        &self,
+        (x, y): (usize, usize),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets
+            .iter()
+            .map(move |&(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .filter(move |&(x, y)| x < width && y < height)
+    }
+
+    /// The positions of the up to four cells sharing an edge with `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.around((x, y), &ORTHOGONAL)
+    }
+
+    /// The positions of the up to eight cells touching `(x, y)`,
+    /// diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.around((x, y), &ALL)
+    }
+
+    /// The same cells, turned into new ones by `f`.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, rows becoming columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(y, height - 1 - x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter anticlockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.cells[self.index_of(x, y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let index = self.index_of(x, y);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+/// The grid as it looks in a puzzle input, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a grid, one line per row, each character mapped to its cell by
+/// `cell`. The grid ends at the first character that isn't a cell, or
+/// at the end of the line before it, so it doesn't take the final
+/// newline. Rows of different lengths fail the parse.
+pub fn parser<T, F>(cell: F) -> impl Fn(&str) -> IResult<&str, Grid<T>>
+where
+    F: Fn(char) -> Option<T>,
+{
+    move |input: &str| {
+        let (input, lines) = separated_list1(tag("\n"), take_while1(|c| cell(c).is_some()))(input)?;
+
+        // A ragged row can't start anything else, so don't backtrack.
+        let width = lines[0].chars().count();
+        if let Some(line) = lines.iter().find(|line| line.chars().count() != width) {
+            return Err(nom::Err::Failure(Error {
+                input: *line,
+                expected: Expected::Kind(ErrorKind::Verify),
+            }));
+        }
+
+        let cells = lines
+            .iter()
+            .flat_map(|line| line.chars())
+            .filter_map(&cell)
+            .collect();
+        Ok((input, Grid::new(cells, width)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u8> {
+        Grid::from_fn(3, 2, |x, y| (y * 3 + x) as u8)
+    }
+
+    #[test]
+    fn can_address_cells() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 5);
+        assert_eq!(grid[4], 4);
+        assert_eq!(grid.get(1, 1), Some(&4));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 0usize.wrapping_add_signed(-1)), None);
+        assert_eq!(grid.position_of(grid.index_of(1, 1)), (1, 1));
+
+        assert_eq!(grid.row(1), [3, 4, 5]);
+        assert_eq!(grid.rows().count(), 2);
+        assert!(grid.column(1).copied().eq([1, 4]));
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &4)));
+    }
+
+    #[test]
+    fn can_find_neighbours() {
+        let grid = digits();
+        let neighbours: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(neighbours, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours4(1, 0).count(), 3);
+        assert_eq!(grid.neighbours8(1, 0).count(), 5);
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+    }
+
+    #[test]
+    fn can_turn_grids() {
+        let grid = digits();
+        assert_eq!(grid.to_string(), "012\n345\n");
+        assert_eq!(grid.transpose().to_string(), "03\n14\n25\n");
+        assert_eq!(grid.rotate_right().to_string(), "30\n41\n52\n");
+        assert_eq!(grid.rotate_left().to_string(), "25\n14\n03\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn can_parse_grids() {
+        let seat = |c| matches!(c, 'L' | '.' | '#').then_some(c);
+        let (rest, grid) = parser(seat)("L.#\n##L\n\nnext").unwrap();
+        assert_eq!(rest, "\n\nnext");
+        assert_eq!(grid.to_string(), "L.#\n##L\n");
+
+        assert!(matches!(parser(seat)("L.L\nLL"), Err(nom::Err::Failure(_))));
+        assert!(parser(seat)("x").is_err());
+    }
+}
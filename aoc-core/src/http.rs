@@ -0,0 +1,193 @@
+//! Talking to the Advent of Code website as a logged-in user, with the
+//! `session` cookie of a browser that is.
+use crate::{store::Fetch, submit::Submit};
+use std::{env, time::Duration};
+
+/// Where the puzzles are, unless pointed at a mock server.
+pub const BASE: &str = "https://adventofcode.com";
+
+/// The environment variable holding the session cookie.
+pub const SESSION: &str = "AOC_SESSION";
+
+/// The environment variable overriding [`BASE`], to try things out
+/// against a mock server.
+pub const URL: &str = "AOC_URL";
+
+/// How long to wait between two requests, to go easy on the servers.
+pub const INTERVAL: Duration = Duration::from_secs(3);
+
+/// A client of the website, authenticated with a session cookie.
+#[derive(Clone)]
+pub struct Http {
+    agent: ureq::Agent,
+    base: String,
+    session: String,
+}
+
+impl Http {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc-core/", env!("CARGO_PKG_VERSION")))
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base: BASE.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// A client with the session from `AOC_SESSION`, if set, talking to
+    /// `AOC_URL` if that's set too.
+    pub fn from_env() -> Option<Self> {
+        let session = env::var(SESSION).ok()?.trim().to_string();
+        if session.is_empty() {
+            return None;
+        }
+        let base = env::var(URL).unwrap_or_else(|_| BASE.to_string());
+        Some(Self::new(session).with_base(base))
+    }
+
+    /// Send the requests to `base` rather than the real website.
+    pub fn with_base(mut self, base: impl Into<String>) -> Self {
+        self.base = base.into();
+        self
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// GET `path`, or `None` if there's nothing there (yet).
+    fn get(&self, path: &str) -> Result<Option<String>, String> {
+        let url = format!("{}{}", self.base, path);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map(Some)
+                .map_err(|err| format!("failed to read {}: {}", url, err)),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(ureq::Error::Status(400, _)) => {
+                Err(format!("{} rejected the session in {}", url, SESSION))
+            }
+            Err(err) => Err(format!("{}: {}", url, err)),
+        }
+    }
+}
+
+impl Fetch for Http {
+    /// Puzzles that haven't unlocked yet are missing rather than errors.
+    fn fetch(&self, year: u16, day: u8) -> Result<Option<String>, String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+}
+
+impl Submit for Http {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/answer", self.base, year, day);
+        self.agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| format!("{}: {}", url, err))?
+            .into_string()
+            .map_err(|err| format!("failed to read {}: {}", url, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// A request as the mock server saw it.
+    #[derive(Debug, Default)]
+    struct Request {
+        line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    /// Serve the canned `(status, body)` responses to one request each
+    /// on a local port, returning its URL and the requests received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = Request::default();
+                reader.read_line(&mut request.line).unwrap();
+
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end().to_string();
+                    if header.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(": ") {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.parse().unwrap();
+                        }
+                    }
+                    request.headers.push(header);
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.body = String::from_utf8(content).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+        (base, server)
+    }
+
+    #[test]
+    fn fetches_with_the_session() {
+        let (base, server) = serve(vec![(200, "1\n2\n"), (404, "Not found"), (400, "")]);
+        let http = Http::new("cookie").with_base(base);
+
+        assert_eq!(http.fetch(2021, 1), Ok(Some("1\n2\n".to_string())));
+        assert_eq!(http.fetch(2021, 25), Ok(None));
+        assert!(http.fetch(2021, 2).unwrap_err().contains(SESSION));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "GET /2021/day/1/input HTTP/1.1\r\n");
+        assert!(requests[0]
+            .headers
+            .contains(&"Cookie: session=cookie".to_string()));
+    }
+
+    #[test]
+    fn submits_answers_as_forms() {
+        let (base, server) = serve(vec![(200, "<p>That's the right answer!")]);
+        let http = Http::new("cookie").with_base(base);
+
+        assert_eq!(
+            http.submit(2021, 3, 2, "a,b c"),
+            Ok("<p>That's the right answer!".to_string())
+        );
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].line, "POST /2021/day/3/answer HTTP/1.1\r\n");
+        assert_eq!(requests[0].body, "level=2&answer=a%2Cb+c");
+    }
+}
@@ -0,0 +1,271 @@
+use generate::Generator;
+use parse::ParseError;
+use std::fmt::Display;
+
+pub mod alloc;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod cli;
+pub mod examples;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod http;
+pub mod numtheory;
+pub mod ocr;
+pub mod output;
+pub mod parse;
+pub mod pathfinding;
+mod runner;
+pub mod scaffold;
+pub mod store;
+pub mod submit;
+mod tables;
+
+pub use runner::{run, run_with, time, Day, Summary, Year};
+
+#[cfg(test)]
+crate::counting_allocator!();
+
+/// A single day's puzzle: how to parse the input and solve both parts.
+///
+/// Day modules don't usually implement this by hand, instead they keep
+/// the `parse_input`/`part1`/`part2` free functions and wire them up
+/// with [`solution!`].
+pub trait Solution {
+    type Input<'a>;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    /// How to generate synthetic inputs, for days that can.
+    const GENERATOR: Option<Generator> = None;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+
+    /// Other ways of solving part 1, by name, which should all agree with
+    /// [`part1`](Self::part1).
+    fn part1_variants() -> Vec<Variant<Solve<Self, Self::Part1>>> {
+        Vec::new()
+    }
+
+    /// Other ways of solving part 2, see
+    /// [`part1_variants`](Self::part1_variants).
+    fn part2_variants() -> Vec<Variant<Solve<Self, Self::Part2>>> {
+        Vec::new()
+    }
+}
+
+/// What a part answers with: a number or some text, or an `Option` or
+/// `Result` of one for solutions that can come up empty. Any other type
+/// that displays as its answer can be wrapped in [`Shown`].
+pub trait Answer {
+    /// The answer as it's checked against the answers file, or why
+    /// there's none.
+    fn answer(&self) -> Result<String, String>;
+}
+
+macro_rules! display_answer {
+    ($($answer:ty),*) => {
+        $(impl Answer for $answer {
+            fn answer(&self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+display_answer!(char, String, &str);
+
+/// An answer given by how `T` displays, for parts whose answer is one
+/// of their own types, like a position shown as `x,y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shown<T>(pub T);
+
+impl<T: Display> Answer for Shown<T> {
+    fn answer(&self) -> Result<String, String> {
+        Ok(self.0.to_string())
+    }
+}
+
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> Result<String, String> {
+        match self {
+            Some(answer) => answer.answer(),
+            None => Err("none found".to_string()),
+        }
+    }
+}
+
+impl<T: Answer, E: Display> Answer for Result<T, E> {
+    fn answer(&self) -> Result<String, String> {
+        match self {
+            Ok(answer) => answer.answer(),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+/// A solution to one of the parts of `S`, giving a `T`.
+pub type Solve<S, T> = for<'a, 'b> fn(&'b <S as Solution>::Input<'a>) -> T;
+
+/// An alternative solution to a part, such as the naive version of an
+/// optimised one. The runner cross-checks and benchmarks variants
+/// against the part's main solution with `--variants`, and the tests do
+/// on every example and generated input.
+#[derive(Debug, Clone, Copy)]
+pub struct Variant<F> {
+    pub name: &'static str,
+    pub solve: F,
+}
+
+/// Declare a `Solver` for the current day module.
+///
+/// The module is expected to provide `parse_input`, `part1` and `part2`
+/// functions, with `parse_input` returning a
+/// `Result<_, aoc_core::parse::ParseError>`. Inputs that borrow from the
+/// puzzle text name their lifetime:
+///
+/// ```ignore
+/// aoc_core::solution! {
+///     type Input<'a> = Vec<&'a [u8]>;
+///     type Part1 = usize;
+///     type Part2 = u64;
+/// }
+/// ```
+///
+/// Alternative solutions are listed by part, as [`Variant`]s named after
+/// their functions, and days that can make up inputs of their own also
+/// declare a [`Generator`], see [`generate`]:
+///
+/// ```ignore
+/// aoc_core::solution! {
+///     type Input = Almanac;
+///     type Part1 = u64;
+///     type Part2 = u64;
+///     const PART2_VARIANTS = [part2_brute_force];
+///     const GENERATOR = Generator { generate, size: 100 };
+/// }
+/// ```
+#[macro_export]
+macro_rules! solution {
+    (
+        type Input<$lt:lifetime> = $input:ty;
+        type Part1 = $part1:ty;
+        type Part2 = $part2:ty;
+        $(const PART1_VARIANTS = [$($variant1:ident),* $(,)?];)?
+        $(const PART2_VARIANTS = [$($variant2:ident),* $(,)?];)?
+        $(const GENERATOR = $generator:expr;)?
+    ) => {
+        pub struct Solver;
+
+        impl $crate::Solution for Solver {
+            type Input<$lt> = $input;
+            type Part1 = $part1;
+            type Part2 = $part2;
+
+            $(const GENERATOR: Option<$crate::generate::Generator> = Some($generator);)?
+
+            fn parse(input: &str) -> Result<Self::Input<'_>, $crate::parse::ParseError> {
+                parse_input(input)
+            }
+
+            fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+                part1(input)
+            }
+
+            fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+                part2(input)
+            }
+
+            $(
+                fn part1_variants() -> Vec<$crate::Variant<$crate::Solve<Self, Self::Part1>>> {
+                    vec![$($crate::Variant {
+                        name: stringify!($variant1),
+                        solve: |input| $variant1(input),
+                    }),*]
+                }
+            )?
+
+            $(
+                fn part2_variants() -> Vec<$crate::Variant<$crate::Solve<Self, Self::Part2>>> {
+                    vec![$($crate::Variant {
+                        name: stringify!($variant2),
+                        solve: |input| $variant2(input),
+                    }),*]
+                }
+            )?
+        }
+    };
+    (
+        type Input = $input:ty;
+        type Part1 = $part1:ty;
+        type Part2 = $part2:ty;
+        $(const PART1_VARIANTS = [$($variant1:ident),* $(,)?];)?
+        $(const PART2_VARIANTS = [$($variant2:ident),* $(,)?];)?
+        $(const GENERATOR = $generator:expr;)?
+    ) => {
+        $crate::solution! {
+            type Input<'a> = $input;
+            type Part1 = $part1;
+            type Part2 = $part2;
+            $(const PART1_VARIANTS = [$($variant1),*];)?
+            $(const PART2_VARIANTS = [$($variant2),*];)?
+            $(const GENERATOR = $generator;)?
+        }
+    };
+}
+
+/// Build the registry of days for a year from their module names.
+#[macro_export]
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        vec![$($crate::Day::new::<$day::Solver>(stringify!($day))),*]
+    };
+}
+
+/// Install [`alloc::Counting`] as the global allocator, for the runner
+/// to report what each step allocates. Goes at the top of a year's
+/// `main.rs`. Without it, the runner reports the memory as n/a.
+#[macro_export]
+macro_rules! counting_allocator {
+    () => {
+        #[global_allocator]
+        static ALLOCATOR: $crate::alloc::Counting = $crate::alloc::Counting;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_can_be_missing() {
+        assert_eq!(42u32.answer(), Ok("42".to_string()));
+        assert_eq!(Some("CMZ").answer(), Ok("CMZ".to_string()));
+        assert_eq!(None::<u32>.answer(), Err("none found".to_string()));
+        assert_eq!(
+            "x".parse::<u32>().answer(),
+            Err("invalid digit found in string".to_string())
+        );
+    }
+
+    #[test]
+    fn answers_can_be_shown() {
+        struct Position(usize, usize);
+
+        impl Display for Position {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{},{}", self.0, self.1)
+            }
+        }
+
+        assert_eq!(Shown(Position(6, 1)).answer(), Ok("6,1".to_string()));
+        assert_eq!(Some(Shown(1.5)).answer(), Ok("1.5".to_string()));
+    }
+}
@@ -0,0 +1,166 @@
+//! Arithmetic on whole numbers, for the puzzles that turn out to be
+//! about divisibility: cycles lining up, clocks wrapping around, and
+//! keys made by repeated multiplication.
+//!
+//! Everything works on `u64`s, going through `u128` wherever a product
+//! could overflow, so moduli can be as large as a `u64` holds.
+use std::collections::HashMap;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, zero if either is.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    match gcd(a, b) {
+        0 => 0,
+        gcd => a / gcd * b,
+    }
+}
+
+/// The greatest common divisor `g` of `a` and `b` along with the
+/// coefficients `x` and `y` making `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r, mut next_r) = (a, b);
+    let (mut x, mut next_x) = (1, 0);
+    let (mut y, mut next_y) = (0, 1);
+    while next_r != 0 {
+        let quotient = r / next_r;
+        (r, next_r) = (next_r, r - quotient * next_r);
+        (x, next_x) = (next_x, x - quotient * next_x);
+        (y, next_y) = (next_y, y - quotient * next_y);
+    }
+    if r < 0 {
+        (-r, -x, -y)
+    } else {
+        (r, x, y)
+    }
+}
+
+fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// The number that multiplied by `a` leaves 1 modulo `m`, if `a` and `m`
+/// are coprime.
+pub fn modinv(a: u64, m: u64) -> Option<u64> {
+    let (gcd, x, _) = extended_gcd((a % m) as i128, m as i128);
+    (gcd == 1).then(|| x.rem_euclid(m as i128) as u64)
+}
+
+/// `base` to the power of `exp`, modulo `m`.
+pub fn modpow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mulmod(result, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// The numbers leaving each of the `(remainder, modulus)` pairs given, as
+/// the smallest of them and the modulus they all repeat with, or `None`
+/// if no number fits them all. The moduli don't need to be coprime.
+///
+/// Panics on a zero modulus, or if the moduli's least common multiple
+/// doesn't fit in a `u64`.
+pub fn crt<I>(congruences: I) -> Option<(u64, u64)>
+where
+    I: IntoIterator<Item = (u64, u64)>,
+{
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            assert!(m2 > 0, "congruence modulo zero");
+            let r2 = r2 % m2;
+            // x = r1 + m1 * k, with m1 * k = r2 - r1 (mod m2), which
+            // takes m1's common factor with m2 to divide the difference.
+            let gcd = gcd(m1, m2);
+            let difference = (r2 as i128 - r1 as i128).rem_euclid(m2 as i128) as u64;
+            if !difference.is_multiple_of(gcd) {
+                return None;
+            }
+            let m = m2 / gcd;
+            let k = mulmod(difference / gcd, modinv(m1 / gcd, m)?, m);
+            let lcm = u64::try_from(m1 as u128 * m as u128).expect("moduli's lcm overflows");
+            Some(((r1 as u128 + m1 as u128 * k as u128) as u64 % lcm, lcm))
+        })
+}
+
+/// The smallest `x` such that `base` to the power of `x` is `target`
+/// modulo `m`, if there's one, for a `base` coprime to `m`. Takes around
+/// the square root of `m` steps, and memory.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    let target = target % m;
+    if target == 1 % m {
+        return Some(0);
+    }
+
+    // Baby steps: every power below n, then giant steps of n at a time
+    // back down from the target until one lands on them.
+    let n = m.isqrt() + 1;
+    let mut powers = HashMap::with_capacity(n as usize);
+    let mut power = 1 % m;
+    for j in 0..n {
+        powers.entry(power).or_insert(j);
+        power = mulmod(power, base, m);
+    }
+
+    let giant = modinv(modpow(base, n, m), m)?;
+    let mut value = target;
+    for i in 0..n {
+        if let Some(j) = powers.get(&value) {
+            return Some(i * n + j);
+        }
+        value = mulmod(value, giant, m);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn does_modular_arithmetic() {
+        assert_eq!(modinv(3, 11), Some(4));
+        assert_eq!(modinv(4, 8), None);
+        assert_eq!(modpow(4, 13, 497), 445);
+        assert_eq!(modpow(2, 0, 1), 0);
+        assert_eq!(modpow(u64::MAX - 1, 2, u64::MAX), 1);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn takes_discrete_logs() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(5, 1, 7), Some(0));
+    }
+}
@@ -0,0 +1,324 @@
+//! Read the capital letters some puzzles render as dot-matrix answers.
+//!
+//! Two fonts show up across the years: a 4x6 one (most of them) and a
+//! larger 6x10 one. The font is picked from the height of the bitmap,
+//! and glyphs are split apart on blank columns.
+use std::{collections::HashSet, fmt, ops::RangeInclusive};
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Why a bitmap couldn't be read, along with the bitmap itself, drawn
+/// with `#` and `.` on the lines after the reason.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// No font is known for a bitmap this tall.
+    UnsupportedHeight { height: usize, bitmap: String },
+    /// Some glyphs didn't match any letter. They're marked as `?` in the
+    /// partially decoded text.
+    Unrecognized {
+        text: String,
+        columns: Vec<RangeInclusive<usize>>,
+        bitmap: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnsupportedHeight { height, bitmap } => {
+                write!(f, "no font is {} pixels tall\n{}", height, bitmap)
+            }
+            Error::Unrecognized {
+                text,
+                columns,
+                bitmap,
+            } => {
+                write!(f, "unrecognized glyphs in {:?} at columns ", text)?;
+                for (idx, range) in columns.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}-{}", range.start(), range.end())?;
+                }
+                write!(f, "\n{}", bitmap)
+            }
+        }
+    }
+}
+
+/// Render the given columns of a bitmap the same way the font tables
+/// are written, so they can be compared directly.
+fn glyph<C, F>(height: usize, columns: C, lit: &F) -> String
+where
+    C: Iterator<Item = usize> + Clone,
+    F: Fn(usize, usize) -> bool,
+{
+    (0..height)
+        .map(|y| {
+            columns
+                .clone()
+                .map(|x| if lit(x, y) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Strip the blank columns a font leaves on either side of narrow
+/// letters, like the `I` of the small font.
+fn trim(glyph: &str) -> String {
+    let rows: Vec<&[u8]> = glyph.lines().map(str::as_bytes).collect();
+    let used = |x: usize| rows.iter().any(|row| row[x] == b'#');
+    let width = rows[0].len();
+
+    let start = (0..width).find(|&x| used(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| used(x)).unwrap_or(0);
+    rows.iter()
+        .map(|row| std::str::from_utf8(&row[start..=end]).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Read the letters out of a `width` by `height` bitmap, where `lit`
+/// tells if the pixel at `(x, y)` is on.
+pub fn read<F>(width: usize, height: usize, lit: F) -> Result<String, Error>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let font = match height {
+        6 => SMALL,
+        10 => LARGE,
+        _ => {
+            return Err(Error::UnsupportedHeight {
+                height,
+                bitmap: glyph(height, 0..width, &lit),
+            })
+        }
+    };
+
+    let used: Vec<bool> = (0..width).map(|x| (0..height).any(|y| lit(x, y))).collect();
+
+    let mut text = String::new();
+    let mut unrecognized = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if !used[x] {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && used[x] {
+            x += 1;
+        }
+        let columns = start..=x - 1;
+
+        let pixels = glyph(height, columns.clone(), &lit);
+        match font.iter().find(|(_, pattern)| trim(pattern) == pixels) {
+            Some(&(letter, _)) => text.push(letter),
+            None => {
+                text.push('?');
+                unrecognized.push(columns);
+            }
+        }
+    }
+
+    if unrecognized.is_empty() {
+        Ok(text)
+    } else {
+        Err(Error::Unrecognized {
+            text,
+            columns: unrecognized,
+            bitmap: glyph(height, 0..width, &lit),
+        })
+    }
+}
+
+/// Read the letters out of a rendered screen, where either `#` or `█`
+/// marks a lit pixel and anything else is off.
+pub fn read_str(screen: &str) -> Result<String, Error> {
+    let rows: Vec<Vec<char>> = screen
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    read(width, rows.len(), |x, y| {
+        matches!(rows[y].get(x), Some('#') | Some('█'))
+    })
+}
+
+/// Read the letters out of a set of lit points. The bitmap is cropped to
+/// the points' bounding box first, so they can have any offset.
+pub fn read_points<I>(points: I) -> Result<String, Error>
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    let points: HashSet<(i64, i64)> = points.into_iter().collect();
+    let min_x = points.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let max_x = points.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+    let min_y = points.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let max_y = points.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+
+    read(
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+        |x, y| points.contains(&(min_x + x as i64, min_y + y as i64)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_small_font() {
+        let screen = "\
+###...##..#..#..##..####.###..####.####.
+#..#.#..#.#.#..#..#.#....#..#.#.......#.
+###..#....##...#..#.###..#..#.###....#..
+#..#.#.##.#.#..####.#....###..#.....#...
+#..#.#..#.#.#..#..#.#....#.#..#....#....
+###...###.#..#.#..#.####.#..#.####.####.";
+
+        assert_eq!(read_str(screen), Ok("BGKAEREZ".to_string()));
+    }
+
+    #[test]
+    fn can_read_large_font() {
+        let screen = LARGE
+            .iter()
+            .map(|(_, pattern)| pattern.lines().collect::<Vec<_>>())
+            .fold(vec![String::new(); 10], |mut rows, glyph| {
+                for (row, line) in rows.iter_mut().zip(glyph) {
+                    row.push_str(line);
+                    row.push_str("..");
+                }
+                rows
+            })
+            .join("\n");
+
+        assert_eq!(read_str(&screen), Ok("ABCEFGHJKLNPRXZ".to_string()));
+    }
+
+    #[test]
+    fn can_read_points() {
+        let points: Vec<(i64, i64)> = (0..6)
+            .map(|y| (0, y))
+            .chain((1..4).map(|x| (x, 5)))
+            .collect();
+
+        assert_eq!(read_points(points.iter().copied()), Ok("L".to_string()));
+        assert_eq!(
+            read_points(points.iter().map(|&(x, y)| (x - 7, y + 20))),
+            Ok("L".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_unrecognized_glyphs() {
+        let screen = "\
+####..#.
+#.....#.
+###...#.
+#.....#.
+#.....#.
+#.....#.";
+
+        assert_eq!(
+            read_str(screen),
+            Err(Error::Unrecognized {
+                text: "F?".to_string(),
+                columns: vec![6..=6],
+                bitmap: screen.to_string(),
+            })
+        );
+        assert_eq!(
+            read_str("#\n#").unwrap_err().to_string(),
+            "no font is 2 pixels tall\n#\n#"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Machine-readable results, one row per answered part, for `--format
+//! json` and `--format csv`. Times are in nanoseconds, and the memory
+//! the part allocated in bytes, left empty when it wasn't counted.
+use crate::{alloc::Usage, answers::Verdict};
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    time::Duration,
+};
+
+/// How the runner reports its results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+/// A single answered part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub parse: Duration,
+    pub solve: Duration,
+    pub memory: Option<Usage>,
+}
+
+const COLUMNS: [&str; 10] = [
+    "year",
+    "day",
+    "part",
+    "answer",
+    "verdict",
+    "parse_ns",
+    "solve_ns",
+    "peak_bytes",
+    "allocated_bytes",
+    "allocations",
+];
+
+fn verdict(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail(_) => "fail",
+        Verdict::Unverified => "unverified",
+        Verdict::Changed(_) => "changed",
+        Verdict::Unknown => "unknown",
+        Verdict::Missing(_) => "missing",
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Quote a CSV field if it holds anything that would break the row.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn fields(row: &Row) -> [String; 10] {
+    let memory = |count: fn(&Usage) -> usize| {
+        row.memory
+            .as_ref()
+            .map_or(String::new(), |usage| count(usage).to_string())
+    };
+    [
+        row.year.to_string(),
+        row.day.to_string(),
+        row.part.to_string(),
+        row.answer.clone(),
+        verdict(&row.verdict).to_string(),
+        row.parse.as_nanos().to_string(),
+        row.solve.as_nanos().to_string(),
+        memory(|usage| usage.peak),
+        memory(|usage| usage.allocated),
+        memory(|usage| usage.allocations),
+    ]
+}
+
+/// Write out all the rows. Nothing is written for plain text, which the
+/// runner prints as it goes.
+pub fn write<W: Write>(out: &mut W, format: Format, rows: &[Row]) -> io::Result<()> {
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            writeln!(out, "[")?;
+            for (idx, row) in rows.iter().enumerate() {
+                let fields = fields(row);
+                let object = COLUMNS
+                    .iter()
+                    .zip(&fields)
+                    .map(|(&column, value)| match column {
+                        "answer" | "verdict" => format!("\"{}\": {}", column, json_string(value)),
+                        _ if value.is_empty() => format!("\"{}\": null", column),
+                        _ => format!("\"{}\": {}", column, value),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let comma = if idx + 1 < rows.len() { "," } else { "" };
+                writeln!(out, "  {{{}}}{}", object, comma)?;
+            }
+            writeln!(out, "]")?;
+        }
+        Format::Csv => {
+            writeln!(out, "{}", COLUMNS.join(","))?;
+            for row in rows {
+                let fields = fields(row).map(|field| csv_field(&field));
+                writeln!(out, "{}", fields.join(","))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                year: 2021,
+                day: 1,
+                part: 1,
+                answer: "1564".to_string(),
+                verdict: Verdict::Pass,
+                parse: Duration::from_nanos(47168),
+                solve: Duration::from_nanos(383),
+                memory: None,
+            },
+            Row {
+                year: 2021,
+                day: 13,
+                part: 2,
+                answer: "#..#\n\"a\",b".to_string(),
+                verdict: Verdict::Unknown,
+                parse: Duration::from_micros(5),
+                solve: Duration::from_micros(12),
+                memory: Some(Usage {
+                    peak: 4096,
+                    allocated: 6144,
+                    allocations: 3,
+                }),
+            },
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut out = Vec::new();
+        write(&mut out, format, &rows()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn can_write_json() {
+        assert_eq!(
+            render(Format::Json),
+            r##"[
+  {"year": 2021, "day": 1, "part": 1, "answer": "1564", "verdict": "pass", "parse_ns": 47168, "solve_ns": 383, "peak_bytes": null, "allocated_bytes": null, "allocations": null},
+  {"year": 2021, "day": 13, "part": 2, "answer": "#..#\n\"a\",b", "verdict": "unknown", "parse_ns": 5000, "solve_ns": 12000, "peak_bytes": 4096, "allocated_bytes": 6144, "allocations": 3}
+]
+"##
+        );
+    }
+
+    #[test]
+    fn can_write_csv() {
+        assert_eq!(
+            render(Format::Csv),
+            "year,day,part,answer,verdict,parse_ns,solve_ns,peak_bytes,allocated_bytes,allocations\n\
+             2021,1,1,1564,pass,47168,383,,,\n\
+             2021,13,2,\"#..#\n\"\"a\"\",b\",unknown,5000,12000,4096,6144,3\n"
+        );
+    }
+}
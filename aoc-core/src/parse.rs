@@ -0,0 +1,276 @@
+//! Parse errors that point back at the offending spot in the puzzle
+//! input, and helpers to produce them from nom and hand-rolled parsers.
+use crate::grid::Grid;
+use nom::{
+    combinator::all_consuming,
+    error::{ErrorKind, FromExternalError},
+    Finish, Parser,
+};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// The offending line.
+    pub snippet: String,
+    /// What the parser was looking for instead.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Build an error for parsing `input` that failed at `at`, which
+    /// must be a slice of `input`.
+    pub fn new(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(0);
+
+        let start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            snippet: input[start..end].to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "    | {}", self.snippet)?;
+        write!(f, "    | {:>1$}", "^", self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What a nom parser was looking for when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    /// The text given to [`tag`].
+    Literal(&'static str),
+    /// The character given to nom's `char`.
+    Char(char),
+    /// Whatever the failing nom parser matches.
+    Kind(ErrorKind),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Literal(text) => write!(f, "{:?}", text),
+            Expected::Char(c) => write!(f, "{:?}", c),
+            Expected::Kind(ErrorKind::Eof) => f.write_str("end of input"),
+            Expected::Kind(ErrorKind::Digit) => f.write_str("digits"),
+            Expected::Kind(ErrorKind::MapRes) => f.write_str("valid value"),
+            Expected::Kind(kind) => f.write_str(&kind.description().to_lowercase()),
+        }
+    }
+}
+
+/// The error type for nom parsers, like nom's own `Error` but keeping
+/// the text a [`tag`] or `char` wanted so [`ParseError`] can name it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<I> {
+    pub input: I,
+    pub expected: Expected,
+}
+
+pub type IResult<I, O> = nom::IResult<I, O, Error<I>>;
+
+impl<I> nom::error::ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Expected::Kind(kind),
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self {
+            input,
+            expected: Expected::Char(c),
+        }
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for Error<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _: E) -> Self {
+        Self {
+            input,
+            expected: Expected::Kind(kind),
+        }
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Error<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at: {}", self.expected, self.input)
+    }
+}
+
+impl<I: fmt::Debug + fmt::Display> std::error::Error for Error<I> {}
+
+/// Match `literal`, like nom's `tag`, but name it in the error if it
+/// isn't there.
+pub fn tag(literal: &'static str) -> impl Fn(&str) -> IResult<&str, &str> {
+    move |input: &str| match input.strip_prefix(literal) {
+        Some(rest) => Ok((rest, &input[..literal.len()])),
+        None => Err(nom::Err::Error(Error {
+            input,
+            expected: Expected::Literal(literal),
+        })),
+    }
+}
+
+/// Run a nom parser over the whole input, failing if it doesn't consume
+/// everything.
+pub fn all<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, Error<&'a str>>,
+{
+    all_consuming(parser)(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::new(input, err.input, err.expected.to_string()))
+}
+
+/// Parse `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(input, token, "number"))
+}
+
+/// Map every byte of every line through `cell`, flattening the rows
+/// into a single buffer.
+pub fn cells<T, F>(input: &str, expected: &str, cell: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(u8) -> Option<T>,
+{
+    let mut cells = Vec::with_capacity(input.len());
+    for line in input.lines() {
+        for (idx, byte) in line.bytes().enumerate() {
+            let at = line.get(idx..).unwrap_or(line);
+            cells.push(cell(byte).ok_or_else(|| ParseError::new(input, at, expected))?);
+        }
+    }
+    Ok(cells)
+}
+
+/// Like [`cells`], for a rectangular grid: every row must be as wide as
+/// the first, which gives the grid's width.
+pub fn grid<T, F>(input: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+where
+    F: Fn(u8) -> Option<T>,
+{
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 {
+        return Err(ParseError::new(input, input, expected));
+    }
+    if let Some(line) = input.lines().find(|line| line.len() != width) {
+        let at = line.get(width..).unwrap_or(line);
+        return Err(ParseError::new(
+            input,
+            at,
+            format!("rows of {} cells", width),
+        ));
+    }
+
+    cells(input, expected, cell).map(|cells| Grid::new(cells, width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{
+        character::complete::{char, digit1},
+        multi::separated_list1,
+        sequence::{separated_pair, terminated},
+    };
+
+    #[test]
+    fn points_at_the_failure() {
+        let input = "1,2,3\n4,x,6\n";
+        let err = all(
+            input,
+            terminated(
+                separated_list1(tag("\n"), separated_list1(tag(","), digit1)),
+                tag("\n"),
+            ),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 2);
+        assert_eq!(err.snippet, "4,x,6");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected \"\\n\"\n    | 4,x,6\n    |  ^"
+        );
+    }
+
+    #[test]
+    fn names_the_expected_text() {
+        let pair = |input| all(input, separated_pair(digit1, tag(" from "), digit1));
+        let err = pair("3 form 5").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        assert_eq!(err.expected, r#"" from ""#);
+
+        let err = all("1\n2", separated_pair(digit1, tag("\n\n"), digit1)).unwrap_err();
+        assert!(err.to_string().contains(r#"expected "\n\n""#));
+
+        let err = all("1;2", separated_pair(digit1, char(','), digit1)).unwrap_err();
+        assert_eq!(err.expected, "','");
+    }
+
+    #[test]
+    fn can_parse_cells() {
+        let digit = |b: u8| b.is_ascii_digit().then(|| b - b'0');
+        assert_eq!(cells("12\n34\n", "digit", digit), Ok(vec![1, 2, 3, 4]));
+
+        let err = cells("12\n3a\n", "digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "digit");
+    }
+
+    #[test]
+    fn grids_are_rectangular() {
+        let digit = |b: u8| b.is_ascii_digit().then(|| b - b'0');
+        assert_eq!(
+            grid("12\n34\n", "digit", digit),
+            Ok(Grid::new(vec![1, 2, 3, 4], 2))
+        );
+
+        let err = grid("12\n345\n", "digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "rows of 2 cells");
+        assert!(grid("", "digit", digit).is_err());
+    }
+
+    #[test]
+    fn can_parse_numbers() {
+        let input = "12 -4";
+        assert_eq!(number::<u32>(input, &input[..2]), Ok(12));
+
+        let err = number::<u32>(input, &input[3..]).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+}
@@ -0,0 +1,441 @@
+//! Shortest paths over anything that can list a node's neighbours.
+//!
+//! A [`Graph`] names its nodes and the edges out of them, and the
+//! searches take any number of sources and stop at the first node
+//! matching their target predicate, or go through the whole graph if
+//! none does. What a search saw is kept in a [`Search`], to read costs
+//! and paths off.
+//!
+//! Graphs that can number their nodes densely, like grids, should say
+//! so with [`Graph::node_count`] and [`Graph::node_index`]: the searches
+//! then keep track of the nodes in a flat table rather than a hash map,
+//! which is a lot faster.
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    ops::Add,
+};
+
+/// A graph, for the searches to walk.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+    /// What walking an edge costs, [`Default`] being nothing.
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    /// Call `edge` with every neighbour of `node` and the cost to get there.
+    fn neighbours<F>(&self, node: Self::Node, edge: F)
+    where
+        F: FnMut(Self::Node, Self::Cost);
+
+    /// How many nodes there are, if they're numbered by [`node_index`](Self::node_index).
+    fn node_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// The number of `node`, below [`node_count`](Self::node_count).
+    /// Only called on graphs with a node count.
+    fn node_index(&self, node: Self::Node) -> usize {
+        let _ = node;
+        unreachable!("graphs with a node count number their nodes")
+    }
+}
+
+/// A graph made up on the fly by a function, see [`from_fn`].
+pub struct FromFn<N, C, F> {
+    neighbours: F,
+    edge: PhantomData<fn(N) -> C>,
+}
+
+/// The graph where `neighbours(node)` lists the neighbours of `node` and
+/// the cost to get to them.
+pub fn from_fn<N, C, F, I>(neighbours: F) -> FromFn<N, C, F>
+where
+    F: Fn(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    FromFn {
+        neighbours,
+        edge: PhantomData,
+    }
+}
+
+impl<N, C, F, I> Graph for FromFn<N, C, F>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: Fn(N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    type Node = N;
+    type Cost = C;
+
+    fn neighbours<E>(&self, node: N, mut edge: E)
+    where
+        E: FnMut(N, C),
+    {
+        for (next, cost) in (self.neighbours)(node) {
+            edge(next, cost);
+        }
+    }
+}
+
+/// How a node was reached: at what cost, and from where, unless it's a
+/// source.
+type Visit<N, C> = (C, Option<N>);
+
+/// The nodes seen so far, by number for graphs that have them. Dense
+/// tables mark the nodes seen in a bitset, which leaves the costs the
+/// searches keep checking as compact as the cost type itself.
+enum Visited<N, C> {
+    Dense {
+        seen: Vec<u64>,
+        costs: Vec<C>,
+        from: Vec<Option<N>>,
+    },
+    Sparse(HashMap<N, Visit<N, C>>),
+}
+
+fn is_set(bits: &[u64], index: usize) -> bool {
+    bits[index / 64] >> (index % 64) & 1 == 1
+}
+
+/// What a search found.
+pub struct Search<'a, G: Graph> {
+    graph: &'a G,
+    visited: Visited<G::Node, G::Cost>,
+    target: Option<G::Node>,
+}
+
+impl<'a, G: Graph> Search<'a, G> {
+    fn new(graph: &'a G) -> Self {
+        let visited = match graph.node_count() {
+            Some(count) => Visited::Dense {
+                seen: vec![0; count.div_ceil(64)],
+                costs: vec![G::Cost::default(); count],
+                from: vec![None; count],
+            },
+            None => Visited::Sparse(HashMap::new()),
+        };
+        Self {
+            graph,
+            visited,
+            target: None,
+        }
+    }
+
+    fn visit(&self, node: G::Node) -> Option<Visit<G::Node, G::Cost>> {
+        match &self.visited {
+            Visited::Dense { from, .. } => {
+                let cost = self.cost(node)?;
+                Some((cost, from[self.graph.node_index(node)]))
+            }
+            Visited::Sparse(visits) => visits.get(&node).copied(),
+        }
+    }
+
+    /// Record reaching `node` at `cost` from `from`, if that's cheaper
+    /// than the best way there so far.
+    fn improve(&mut self, node: G::Node, cost: G::Cost, from: Option<G::Node>) -> bool {
+        match &mut self.visited {
+            Visited::Dense {
+                seen,
+                costs,
+                from: froms,
+            } => {
+                let index = self.graph.node_index(node);
+                if is_set(seen, index) && costs[index] <= cost {
+                    return false;
+                }
+                seen[index / 64] |= 1 << (index % 64);
+                costs[index] = cost;
+                froms[index] = from;
+            }
+            Visited::Sparse(visits) => {
+                let best = visits.get(&node).map(|&(best, _)| best);
+                if best.is_some_and(|best| best <= cost) {
+                    return false;
+                }
+                visits.insert(node, (cost, from));
+            }
+        }
+        true
+    }
+
+    /// The target the search stopped at, with its cost.
+    pub fn target(&self) -> Option<(G::Node, G::Cost)> {
+        let target = self.target?;
+        Some((target, self.cost(target)?))
+    }
+
+    /// The cost of the cheapest path found to `node`.
+    pub fn cost(&self, node: G::Node) -> Option<G::Cost> {
+        match &self.visited {
+            Visited::Dense { seen, costs, .. } => {
+                let index = self.graph.node_index(node);
+                is_set(seen, index).then(|| costs[index])
+            }
+            Visited::Sparse(visits) => visits.get(&node).map(|&(cost, _)| cost),
+        }
+    }
+
+    /// The cheapest path found to `node`, from the source it starts at to
+    /// `node` itself.
+    pub fn path(&self, node: G::Node) -> Option<Vec<G::Node>> {
+        let mut path = vec![node];
+        let (_, mut from) = self.visit(node)?;
+        while let Some(node) = from {
+            path.push(node);
+            from = self.visit(node)?.1;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The cheapest path to the target the search stopped at.
+    pub fn target_path(&self) -> Option<Vec<G::Node>> {
+        self.path(self.target?)
+    }
+}
+
+/// A node waiting in the queue, the one with the lowest estimate of the
+/// total cost through it coming out first.
+struct Queued<N, C> {
+    estimate: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+/// Search for the cheapest path from any of `sources` to a node that
+/// `is_target`, exploring the cheapest nodes first. `heuristic`
+/// estimates the cost left from a node to the closest target, which it
+/// must never overestimate for the path found to be the cheapest.
+pub fn astar<G, S, T, H>(graph: &G, sources: S, mut is_target: T, mut heuristic: H) -> Search<'_, G>
+where
+    G: Graph,
+    S: IntoIterator<Item = G::Node>,
+    T: FnMut(G::Node) -> bool,
+    H: FnMut(G::Node) -> G::Cost,
+{
+    let mut search = Search::new(graph);
+    let mut queue = BinaryHeap::new();
+    for node in sources {
+        let cost = G::Cost::default();
+        if search.improve(node, cost, None) {
+            let estimate = heuristic(node);
+            queue.push(Queued { estimate, node });
+        }
+    }
+
+    while let Some(Queued { estimate, node }) = queue.pop() {
+        // Nodes queue up again when a cheaper way to them turns up, and
+        // the estimate then says which entry is the stale one.
+        let cost = search.cost(node).unwrap();
+        if cost + heuristic(node) < estimate {
+            continue;
+        }
+        if is_target(node) {
+            search.target = Some(node);
+            break;
+        }
+
+        graph.neighbours(node, |next, step| {
+            let cost = cost + step;
+            if search.improve(next, cost, Some(node)) {
+                queue.push(Queued {
+                    estimate: cost + heuristic(next),
+                    node: next,
+                });
+            }
+        });
+    }
+    search
+}
+
+/// Search for the cheapest path from any of `sources` to a node that
+/// `is_target`, exploring the cheapest nodes first.
+pub fn dijkstra<G, S, T>(graph: &G, sources: S, is_target: T) -> Search<'_, G>
+where
+    G: Graph,
+    S: IntoIterator<Item = G::Node>,
+    T: FnMut(G::Node) -> bool,
+{
+    astar(graph, sources, is_target, |_| G::Cost::default())
+}
+
+/// Search for the path with the fewest edges from any of `sources` to a
+/// node that `is_target`, ignoring the edges' costs. The costs the
+/// search reports are the edges taken, counted in the graph's cost type
+/// with `one`.
+pub fn bfs<G, S, T>(graph: &G, sources: S, mut is_target: T, one: G::Cost) -> Search<'_, G>
+where
+    G: Graph,
+    S: IntoIterator<Item = G::Node>,
+    T: FnMut(G::Node) -> bool,
+{
+    let mut search = Search::new(graph);
+    let mut queue = VecDeque::new();
+    for node in sources {
+        if search.improve(node, G::Cost::default(), None) {
+            queue.push_back(node);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_target(node) {
+            search.target = Some(node);
+            break;
+        }
+
+        let steps = search.cost(node).unwrap() + one;
+        graph.neighbours(node, |next, _| {
+            if search.visit(next).is_none() {
+                search.improve(next, steps, Some(node));
+                queue.push_back(next);
+            }
+        });
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A maze of open (`.`) and walled (`#`) cells, costing more to walk
+    /// through the cells marked with a digit.
+    struct Maze {
+        cells: Vec<u8>,
+        width: usize,
+    }
+
+    const MAZE: &str = "\
+..9..#
+.###.#
+.#...#
+.#.#.#
+...#..";
+
+    impl Maze {
+        fn new(maze: &str) -> Self {
+            Self {
+                cells: maze.bytes().filter(|&b| b != b'\n').collect(),
+                width: maze.find('\n').unwrap(),
+            }
+        }
+    }
+
+    impl Graph for Maze {
+        type Node = usize;
+        type Cost = u32;
+
+        fn neighbours<F: FnMut(usize, u32)>(&self, node: usize, mut edge: F) {
+            let (x, y) = (node % self.width, node / self.width);
+            let height = self.cells.len() / self.width;
+            let around = [
+                (x > 0).then(|| node - 1),
+                (x + 1 < self.width).then(|| node + 1),
+                (y > 0).then(|| node - self.width),
+                (y + 1 < height).then(|| node + self.width),
+            ];
+            for next in around.into_iter().flatten() {
+                match self.cells[next] {
+                    b'#' => {}
+                    b'.' => edge(next, 1),
+                    digit => edge(next, (digit - b'0') as u32),
+                }
+            }
+        }
+
+        fn node_count(&self) -> Option<usize> {
+            Some(self.cells.len())
+        }
+
+        fn node_index(&self, node: usize) -> usize {
+            node
+        }
+    }
+
+    #[test]
+    fn finds_the_cheapest_path() {
+        let maze = Maze::new(MAZE);
+        let search = dijkstra(&maze, [0], |node| node == 29);
+        assert_eq!(search.target(), Some((29, 13)));
+        assert_eq!(
+            search.target_path(),
+            Some(vec![0, 6, 12, 18, 24, 25, 26, 20, 14, 15, 16, 22, 28, 29])
+        );
+
+        // Over the 9 rather than around it.
+        let shortest = bfs(&maze, [0], |node| node == 29, 1);
+        assert_eq!(shortest.target(), Some((29, 9)));
+        assert_eq!(
+            shortest.target_path(),
+            Some(vec![0, 1, 2, 3, 4, 10, 16, 22, 28, 29])
+        );
+    }
+
+    #[test]
+    fn heuristics_find_the_same_cost() {
+        let maze = Maze::new(MAZE);
+        let (x, y) = (29 % maze.width, 29 / maze.width);
+        let manhattan = |node: usize| {
+            let (nx, ny) = (node % maze.width, node / maze.width);
+            (nx.abs_diff(x) + ny.abs_diff(y)) as u32
+        };
+        let search = astar(&maze, [0], |node| node == 29, manhattan);
+        assert_eq!(search.target(), Some((29, 13)));
+    }
+
+    #[test]
+    fn searches_from_and_to_many_nodes() {
+        let maze = Maze::new(MAZE);
+        let search = bfs(&maze, [0, 28], |_| false, 1);
+        assert_eq!(search.target(), None);
+        assert_eq!(search.cost(24), Some(4));
+        assert_eq!(search.cost(5), None);
+        assert_eq!(search.path(20), Some(vec![28, 22, 16, 15, 14, 20]));
+
+        let search = dijkstra(&maze, [24], |node| [4, 29].contains(&node));
+        assert_eq!(search.target(), Some((4, 8)));
+    }
+
+    #[test]
+    fn graphs_can_be_functions() {
+        // Reaching 1 from 27 by halving even numbers and tripling and
+        // adding one to odd ones takes 111 steps.
+        let collatz = from_fn(|n: u64| {
+            [(
+                if n.is_multiple_of(2) {
+                    n / 2
+                } else {
+                    3 * n + 1
+                },
+                1u32,
+            )]
+        });
+        let search = bfs(&collatz, [27], |n| n == 1, 1);
+        assert_eq!(search.target(), Some((1, 111)));
+        assert_eq!(search.target_path().map(|path| path.len()), Some(112));
+    }
+}
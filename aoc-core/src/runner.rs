@@ -0,0 +1,911 @@
+use crate::{
+    alloc::{self, Usage},
+    answers::{Answers, Expected, Verdict},
+    baseline::{Baseline, Step},
+    bench::{self, Stats},
+    cli::{self, Command, Options},
+    generate::{Generator, Rng},
+    http::{self, Http},
+    output::{self, Format, Row},
+    parse::ParseError,
+    scaffold,
+    store::{self, RateLimited, Store},
+    submit::{self, Outcome},
+    Answer, Solution, Solve, Variant,
+};
+use std::{
+    env, fmt, fs, io,
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+pub fn time<F, R>(f: F) -> (Duration, R)
+where
+    F: FnOnce() -> R,
+{
+    let now = Instant::now();
+    let result = f();
+    (now.elapsed(), result)
+}
+
+/// How long a step took: a single run, or many of them with `--bench`.
+enum Timing {
+    Once(Duration),
+    Bench(Stats),
+}
+
+impl Timing {
+    /// The run time to track: the median when benchmarking.
+    fn duration(&self) -> Duration {
+        match self {
+            Timing::Once(elapsed) => *elapsed,
+            Timing::Bench(stats) => stats.median,
+        }
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timing::Once(elapsed) => write!(f, "{:?}", elapsed),
+            Timing::Bench(stats) => write!(f, "{}", stats),
+        }
+    }
+}
+
+/// Run `f` once, timing it and counting what it allocates, and
+/// benchmark it further if asked to.
+fn timed<F, R>(options: &Options, bytes: usize, mut f: F) -> (Timing, Option<Usage>, R)
+where
+    F: FnMut() -> R,
+{
+    let (memory, (elapsed, result)) = alloc::measure(|| time(&mut f));
+    let timing = if options.bench {
+        Timing::Bench(bench::measure(bytes, f))
+    } else {
+        Timing::Once(elapsed)
+    };
+    (timing, memory, result)
+}
+
+/// What a step allocated, or n/a when the allocations aren't counted.
+struct Memory(Option<Usage>);
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(usage) => write!(f, "{}", usage),
+            None => write!(f, "memory n/a"),
+        }
+    }
+}
+
+struct Part {
+    /// The answer, or why there's none.
+    answer: Result<String, String>,
+    elapsed: Timing,
+    memory: Option<Usage>,
+    variants: Vec<Alternative>,
+}
+
+/// How a variant of a part did, with `--variants`.
+struct Alternative {
+    name: &'static str,
+    answer: Result<String, String>,
+    elapsed: Timing,
+    memory: Option<Usage>,
+}
+
+struct Report {
+    parse: Timing,
+    parse_memory: Option<Usage>,
+    part1: Option<Part>,
+    part2: Option<Part>,
+}
+
+impl Report {
+    /// The variants that didn't give the same answer as their part.
+    fn mismatches(&self) -> Vec<Mismatch> {
+        [(1, &self.part1), (2, &self.part2)]
+            .into_iter()
+            .filter_map(|(number, part)| Some((number, part.as_ref()?)))
+            .flat_map(|(number, part)| {
+                part.variants
+                    .iter()
+                    .filter(|variant| variant.answer != part.answer)
+                    .map(move |variant| Mismatch {
+                        part: number,
+                        variant: variant.name,
+                        expected: part.answer.clone(),
+                        answer: variant.answer.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// A variant disagreeing with its part's main solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Mismatch {
+    pub part: u8,
+    pub variant: &'static str,
+    pub expected: Result<String, String>,
+    pub answer: Result<String, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |answer: &Result<String, String>| match answer {
+            Ok(answer) => format!("{:?}", answer),
+            Err(reason) => format!("nothing ({})", reason),
+        };
+        write!(
+            f,
+            "{} answered {}, but part{} answered {}",
+            self.variant,
+            describe(&self.answer),
+            self.part,
+            describe(&self.expected)
+        )
+    }
+}
+
+fn solve_part<S, T>(
+    options: &Options,
+    bytes: usize,
+    input: &S::Input<'_>,
+    main: Solve<S, T>,
+    variants: Vec<Variant<Solve<S, T>>>,
+) -> Part
+where
+    S: Solution,
+    T: Answer,
+{
+    let (elapsed, memory, answer) = timed(options, bytes, || main(input));
+    let variants = match options.variants {
+        true => variants
+            .into_iter()
+            .map(|variant| {
+                let (elapsed, memory, answer) = timed(options, bytes, || (variant.solve)(input));
+                Alternative {
+                    name: variant.name,
+                    answer: answer.answer(),
+                    elapsed,
+                    memory,
+                }
+            })
+            .collect(),
+        false => Vec::new(),
+    };
+    Part {
+        answer: answer.answer(),
+        elapsed,
+        memory,
+        variants,
+    }
+}
+
+fn solve<S: Solution>(input: &str, options: &Options) -> Result<Report, ParseError> {
+    let bytes = input.len();
+    let (parse_memory, (elapsed, parsed)) = alloc::measure(|| time(|| S::parse(input)));
+    // Only benchmark parsing once it's known to succeed.
+    let parse = match options.bench {
+        true if parsed.is_ok() => Timing::Bench(bench::measure(bytes, || S::parse(input))),
+        _ => Timing::Once(elapsed),
+    };
+    let input = parsed?;
+
+    let part1 = options
+        .runs_part(1)
+        .then(|| solve_part::<S, _>(options, bytes, &input, S::part1, S::part1_variants()));
+
+    let part2 = options
+        .runs_part(2)
+        .then(|| solve_part::<S, _>(options, bytes, &input, S::part2, S::part2_variants()));
+
+    Ok(Report {
+        parse,
+        parse_memory,
+        part1,
+        part2,
+    })
+}
+
+const fn day_number(name: &str) -> u8 {
+    let bytes = name.as_bytes();
+    let mut day = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx].is_ascii_digit() {
+            day = day * 10 + (bytes[idx] - b'0');
+        }
+        idx += 1;
+    }
+    day
+}
+
+/// A registered day, with its solution erased behind a function pointer.
+pub struct Day {
+    pub day: u8,
+    pub name: &'static str,
+    pub generator: Option<Generator>,
+    solve: fn(&str, &Options) -> Result<Report, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(name: &'static str) -> Self {
+        Self {
+            day: day_number(name),
+            name,
+            generator: S::GENERATOR,
+            solve: solve::<S>,
+        }
+    }
+
+    /// Solve both parts of `input` once along with their variants,
+    /// keeping the variants that disagreed.
+    pub(crate) fn cross_check(&self, input: &str) -> Result<Vec<Mismatch>, ParseError> {
+        let options = Options {
+            variants: true,
+            ..Options::default()
+        };
+        (self.solve)(input, &options).map(|report| report.mismatches())
+    }
+}
+
+/// Every day solved for a given year.
+pub struct Year {
+    pub year: u16,
+    pub days: Vec<Day>,
+}
+
+/// The time every year's days should run in, all together.
+const BUDGET: Duration = Duration::from_secs(1);
+
+/// How many answers passed, failed, only matched the recorded ones or
+/// couldn't be checked, how many
+/// variants disagreed with them, how many days were skipped for lack of
+/// an input or couldn't parse it, and how the run times compared to the
+/// baseline and budget.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unverified: usize,
+    pub unknown: usize,
+    pub mismatches: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    pub regressions: usize,
+    pub over_budget: bool,
+}
+
+impl Summary {
+    fn record(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail(_) | Verdict::Changed(_) | Verdict::Missing(_) => self.failed += 1,
+            Verdict::Unverified => self.unverified += 1,
+            Verdict::Unknown => self.unknown += 1,
+        }
+    }
+}
+
+/// Run the days selected on the command line, exiting with a failure if
+/// any input fails to parse, any answer doesn't match the answers files or
+/// any variant disagrees with its part.
+/// With `--compare`, slowdowns and going over budget fail the run too.
+pub fn run(year: Year) {
+    let command = match Command::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err @ cli::Error::Help) => {
+            println!("{}", err);
+            return;
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+
+    let selected = match &command {
+        Command::Run(options) => options.year,
+        Command::NewDay { year, .. } | Command::Submit { year, .. } => *year,
+    };
+    if let Some(selected) = selected.filter(|&selected| selected != year.year) {
+        eprintln!(
+            "error: no solutions for {}, this runner only solves {}",
+            selected, year.year
+        );
+        process::exit(2);
+    }
+
+    let options = match command {
+        Command::Run(options) => options,
+        Command::NewDay { day, .. } => {
+            new_day(&year, day);
+            return;
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            inputs,
+            ..
+        } => {
+            if !submit(&year, day, part, answer, inputs) {
+                process::exit(1);
+            }
+            return;
+        }
+    };
+
+    if options.emit {
+        if !emit(&year, &options) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let summary = run_with(&year, &options);
+    let slow = options.compare && (summary.regressions > 0 || summary.over_budget);
+    if summary.failed > 0 || summary.mismatches > 0 || summary.errors > 0 || slow {
+        process::exit(1);
+    }
+}
+
+/// Scaffold `day` in the year's crate, for `new-day`.
+fn new_day(year: &Year, day: u8) {
+    let root = store::workspace().join(format!("aoc-{}", year.year));
+    match scaffold::new_day(&root, &store::dir(year.year), day) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Submit the answer to a part, the given one or else its solution's,
+/// for `submit`. A right answer is recorded in the answers file.
+fn submit(year: &Year, day: u8, part: u8, answer: Option<String>, inputs: Option<PathBuf>) -> bool {
+    let Some(client) = Http::from_env() else {
+        eprintln!(
+            "error: submitting needs a session cookie in {}",
+            http::SESSION
+        );
+        return false;
+    };
+    let options = Options {
+        part: Some(part),
+        inputs,
+        ..Options::default()
+    };
+    let store = open_store(year, &options);
+
+    let answer = match answer.map_or_else(|| solve_selected(year, &store, day, &options), Ok) {
+        Ok(answer) => answer,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return false;
+        }
+    };
+
+    let path = store.dir().join("answers.toml");
+    let mut answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("error: can't read {}: {}", path.display(), err);
+            return false;
+        }
+    };
+    match answers.get(day, part) {
+        Some(expected) if expected.matches(&answer) => {
+            println!("{} is already known to be right", answer);
+            return true;
+        }
+        Some(expected) => {
+            let refusal = submit::Refusal::Expected(expected.to_string());
+            eprintln!("error: {}", submit::Error::Refused(refusal));
+            return false;
+        }
+        None => {}
+    }
+
+    println!("{} day{:02} part {}: {}", year.year, day, part, answer);
+    let log = store.dir().join("attempts.log");
+    match submit::submit(&client, &log, year.year, day, part, &answer) {
+        Ok(Outcome::Correct) => {
+            println!("that's the right answer");
+            answers.record(day, part, Expected::new(&answer));
+            if let Err(err) = answers.save(&path) {
+                eprintln!("error: can't save {}: {}", path.display(), err);
+                return false;
+            }
+            println!("recorded in {}", path.display());
+            true
+        }
+        Ok(outcome) => {
+            match outcome {
+                Outcome::TooHigh => println!("that's not the right answer, it's too high"),
+                Outcome::TooLow => println!("that's not the right answer, it's too low"),
+                Outcome::Wait(wait) => {
+                    println!("answered too recently, wait {}s", wait.as_secs())
+                }
+                Outcome::WrongLevel => println!("that part is already solved, or still locked"),
+                _ => println!("that's not the right answer"),
+            }
+            false
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            false
+        }
+    }
+}
+
+/// Solve the part selected in `options` on `day`'s input.
+fn solve_selected(
+    year: &Year,
+    store: &Store,
+    day: u8,
+    options: &Options,
+) -> Result<String, String> {
+    let solution = year
+        .days
+        .iter()
+        .find(|solution| solution.day == day)
+        .ok_or_else(|| format!("no solution for {} day{:02}", year.year, day))?;
+    let input = store.input(year.year, day).map_err(|err| err.to_string())?;
+    let report = (solution.solve)(&input, options)
+        .map_err(|err| format!("failed to parse the input\n{}", err))?;
+
+    let part = match options.part {
+        Some(1) => report.part1,
+        _ => report.part2,
+    };
+    part.map_or_else(|| Err("no part selected".to_string()), |part| part.answer)
+        .map_err(|reason| format!("no answer: {}", reason))
+}
+
+/// Where to read the inputs from: the year's own store, or the one
+/// picked with `--inputs`. Missing inputs are copied over from
+/// `--fetch-from`, or else downloaded into the year's own store if
+/// there's a session to do it with.
+fn open_store(year: &Year, options: &Options) -> Store {
+    let store = Store::new(
+        options
+            .inputs
+            .clone()
+            .unwrap_or_else(|| store::dir(year.year)),
+    );
+    if let Some(dir) = &options.fetch_from {
+        store.with_fetcher(store::Dir(dir.clone()))
+    } else if let (None, Some(http)) = (&options.inputs, Http::from_env()) {
+        store.with_fetcher(RateLimited::new(http, http::INTERVAL))
+    } else {
+        store
+    }
+}
+
+/// Print progress for humans. It goes to stderr when stdout is taken by
+/// machine-readable output.
+macro_rules! say {
+    ($options:expr) => {
+        say!($options, "")
+    };
+    ($options:expr, $($arg:tt)*) => {
+        if $options.format == Format::Text {
+            println!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Print the input generated for the selected day, for `--emit`.
+fn emit(year: &Year, options: &Options) -> bool {
+    let Some(day) = year.days.iter().find(|day| options.runs_day(day.day)) else {
+        eprintln!("error: no solution for the selected day");
+        return false;
+    };
+    let (Some(generator), Some(size)) = (day.generator, options.generate) else {
+        eprintln!(
+            "error: {} day{:02} has no input generator",
+            year.year, day.day
+        );
+        return false;
+    };
+
+    print!(
+        "{}",
+        (generator.generate)(&mut Rng::new(options.seed()), size)
+    );
+    true
+}
+
+/// A day's input, ready to solve, or why the day is skipped.
+enum Job {
+    Read(String, String),
+    Generated(String, String),
+    Skipped(String),
+}
+
+impl Job {
+    fn new(year: &Year, day: &Day, store: &Store, options: &Options) -> Self {
+        if let Some(size) = options.generate {
+            let Some(generator) = day.generator else {
+                return Job::Skipped("no input generator".to_string());
+            };
+            let source = format!("generated input (size {}, seed {})", size, options.seed());
+            let input = (generator.generate)(&mut Rng::new(options.seed()), size);
+            return Job::Generated(source, input);
+        }
+
+        let (path, input) = match &options.input {
+            Some(path) => (
+                path.clone(),
+                fs::read_to_string(path).map_err(|err| store::Error::Io(path.clone(), err)),
+            ),
+            None => (store.path(day.day), store.input(year.year, day.day)),
+        };
+        match input {
+            Ok(input) => Job::Read(path.display().to_string(), input),
+            Err(err) => Job::Skipped(err.to_string()),
+        }
+    }
+}
+
+/// How many threads `--parallel` solves on.
+fn pool_size() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// Solve every day with an input on a pool of `threads` threads, each
+/// taking the next day to solve as soon as it's done with the last one.
+/// The reports come back in the days' order.
+fn solve_all(
+    jobs: &[(&Day, Job)],
+    options: &Options,
+    threads: usize,
+) -> Vec<Option<Result<Report, ParseError>>> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(jobs.iter().map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.min(jobs.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some((day, job)) = jobs.get(idx) else {
+                    break;
+                };
+                if let Job::Read(_, input) | Job::Generated(_, input) = job {
+                    let report = (day.solve)(input, options);
+                    reports.lock().unwrap()[idx] = Some(report);
+                }
+            });
+        }
+    });
+    reports.into_inner().unwrap()
+}
+
+pub fn run_with(year: &Year, options: &Options) -> Summary {
+    let now = Instant::now();
+    let mut summary = Summary::default();
+
+    let store = open_store(year, options);
+    let inputs = store.dir();
+
+    // The answers only hold for the inputs they sit next to, so there's
+    // nothing to check a hand-picked or generated input against.
+    let load_answers = |name: &str| {
+        let path = inputs.join(name);
+        if options.input.is_some() || options.generate.is_some() {
+            return Answers::default();
+        }
+        Answers::load(&path).unwrap_or_else(|err| {
+            eprintln!("warning: ignoring {}: {}", path.display(), err);
+            Answers::default()
+        })
+    };
+    let answers = load_answers("answers.toml");
+    let recorded = load_answers("recorded.toml");
+
+    let baseline_path = inputs.join("baseline.toml");
+    let mut baseline = if options.compare || options.save_baseline {
+        Baseline::load(&baseline_path).unwrap_or_else(|err| {
+            eprintln!("warning: ignoring {}: {}", baseline_path.display(), err);
+            Baseline::default()
+        })
+    } else {
+        Baseline::default()
+    };
+    let mut regressions = Vec::new();
+    let mut spent = Duration::ZERO;
+    let mut rows = Vec::new();
+
+    for day in options.days.iter().flat_map(|days| days.iter()) {
+        if !year.days.iter().any(|registered| registered.day == day) {
+            eprintln!("warning: no solution for {} day{:02}", year.year, day);
+        }
+    }
+
+    // Gather the inputs first, so that the days can then be solved all
+    // at once with `--parallel`.
+    let jobs: Vec<(&Day, Job)> = year
+        .days
+        .iter()
+        .filter(|day| options.runs_day(day.day))
+        .map(|day| (day, Job::new(year, day, &store, options)))
+        .collect();
+
+    let (mut reports, parallel) = match options.parallel.then(pool_size) {
+        Some(threads) => {
+            let (elapsed, reports) = time(|| solve_all(&jobs, options, threads));
+            (reports, Some((threads, elapsed)))
+        }
+        None => (jobs.iter().map(|_| None).collect(), None),
+    };
+
+    for ((day, job), report) in jobs.iter().zip(reports.iter_mut()) {
+        say!(options, ":: {} day{:02}", year.year, day.day);
+
+        let (source, input) = match job {
+            Job::Skipped(reason) => {
+                summary.skipped += 1;
+                say!(options, "skipped: {}", reason);
+                say!(options);
+                continue;
+            }
+            Job::Generated(source, input) => {
+                say!(options, "input: {}", source);
+                (source, input)
+            }
+            Job::Read(source, input) => (source, input),
+        };
+
+        let report = match report.take().unwrap_or_else(|| (day.solve)(input, options)) {
+            Ok(report) => report,
+            Err(err) => {
+                summary.errors += 1;
+                say!(options, "error: failed to parse {}", source);
+                say!(options, "{}", err);
+                say!(options);
+                continue;
+            }
+        };
+        say!(
+            options,
+            "parse: {} ({})",
+            report.parse,
+            Memory(report.parse_memory)
+        );
+        let mut timings = vec![(Step::Parse, report.parse.duration())];
+        for (number, part) in [(1, report.part1), (2, report.part2)] {
+            if let Some(part) = part {
+                timings.push((Step::Part(number), part.elapsed.duration()));
+                let verdict = match &part.answer {
+                    Ok(answer) => match answers.check(day.day, number, answer) {
+                        Verdict::Unknown => recorded.check_recorded(day.day, number, answer),
+                        verdict => verdict,
+                    },
+                    Err(reason) => Verdict::Missing(reason.clone()),
+                };
+                let answer = part.answer.clone().unwrap_or_default();
+                summary.record(&verdict);
+                rows.push(Row {
+                    year: year.year,
+                    day: day.day,
+                    part: number,
+                    answer: answer.clone(),
+                    verdict: verdict.clone(),
+                    parse: report.parse.duration(),
+                    solve: part.elapsed.duration(),
+                    memory: part.memory,
+                });
+                if answer.is_empty() || answer.contains('\n') {
+                    say!(
+                        options,
+                        "part{}: ({}; {}) [{}]",
+                        number,
+                        part.elapsed,
+                        Memory(part.memory),
+                        verdict
+                    );
+                    for line in answer.lines() {
+                        say!(options, "    {}", line);
+                    }
+                } else {
+                    say!(
+                        options,
+                        "part{}: {} ({}; {}) [{}]",
+                        number,
+                        answer,
+                        part.elapsed,
+                        Memory(part.memory),
+                        verdict
+                    );
+                }
+                for variant in &part.variants {
+                    let agrees = variant.answer == part.answer;
+                    if !agrees {
+                        summary.mismatches += 1;
+                    }
+                    let verdict = if agrees { "AGREES" } else { "DISAGREES" };
+                    let answer = variant.answer.clone().unwrap_or_default();
+                    if answer.is_empty() || answer.contains('\n') {
+                        say!(
+                            options,
+                            "    {}: ({}; {}) [{}]",
+                            variant.name,
+                            variant.elapsed,
+                            Memory(variant.memory),
+                            verdict
+                        );
+                    } else {
+                        say!(
+                            options,
+                            "    {}: {} ({}; {}) [{}]",
+                            variant.name,
+                            answer,
+                            variant.elapsed,
+                            Memory(variant.memory),
+                            verdict
+                        );
+                    }
+                }
+            }
+        }
+        say!(options);
+
+        for (step, elapsed) in timings {
+            spent += elapsed;
+            if options.compare {
+                regressions.extend(baseline.compare(day.day, step, elapsed, options.threshold()));
+            }
+            baseline.record(day.day, step, elapsed);
+        }
+    }
+
+    if let Err(err) = output::write(&mut io::stdout().lock(), options.format, &rows) {
+        eprintln!("error: failed to write results: {}", err);
+    }
+
+    say!(options, "total: {:?}", now.elapsed());
+    summary.over_budget = spent > BUDGET;
+    say!(
+        options,
+        "budget: {:?} of {:?}{}",
+        spent,
+        BUDGET,
+        if summary.over_budget {
+            ", OVER BUDGET"
+        } else {
+            ""
+        }
+    );
+    if let Some((threads, elapsed)) = parallel {
+        say!(
+            options,
+            "parallel: {:?} on {} thread{}, {:.1}x the days' {:?} one after another",
+            elapsed,
+            threads,
+            if threads == 1 { "" } else { "s" },
+            spent.as_secs_f64() / elapsed.as_secs_f64(),
+            spent
+        );
+    }
+    say!(
+        options,
+        "answers: {} passed, {} failed, {} unverified, {} unknown",
+        summary.passed,
+        summary.failed,
+        summary.unverified,
+        summary.unknown
+    );
+    if options.variants {
+        say!(options, "variants: {} disagreed", summary.mismatches);
+    }
+    if summary.skipped > 0 {
+        say!(
+            options,
+            "skipped: {} days without an input",
+            summary.skipped
+        );
+    }
+    if summary.errors > 0 {
+        say!(options, "parse errors: {}", summary.errors);
+    }
+
+    if options.compare {
+        summary.regressions = regressions.len();
+        say!(
+            options,
+            "baseline: {} regressions over {}%",
+            regressions.len(),
+            options.threshold()
+        );
+        for regression in &regressions {
+            say!(options, "    {}", regression);
+        }
+    }
+    if options.save_baseline {
+        match baseline.save(&baseline_path) {
+            Ok(()) => say!(options, "baseline: saved to {}", baseline_path.display()),
+            Err(err) => eprintln!(
+                "warning: failed to save {}: {}",
+                baseline_path.display(),
+                err
+            ),
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod day01 {
+        use crate::parse::ParseError;
+
+        crate::solution! {
+            type Input = Vec<u32>;
+            type Part1 = u32;
+            type Part2 = u32;
+            const PART1_VARIANTS = [part1_loop];
+            const PART2_VARIANTS = [part2_off_by_one];
+        }
+
+        pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+            input
+                .lines()
+                .map(|line| crate::parse::number(input, line))
+                .collect()
+        }
+
+        pub fn part1(input: &[u32]) -> u32 {
+            input.iter().sum()
+        }
+
+        pub fn part1_loop(input: &[u32]) -> u32 {
+            let mut sum = 0;
+            for n in input {
+                sum += n;
+            }
+            sum
+        }
+
+        pub fn part2(input: &[u32]) -> u32 {
+            input.iter().max().copied().unwrap_or(0)
+        }
+
+        pub fn part2_off_by_one(input: &[u32]) -> u32 {
+            part2(input) + 1
+        }
+    }
+
+    #[test]
+    fn cross_checks_variants() {
+        let day = Day::new::<day01::Solver>("day01");
+        assert_eq!(
+            day.cross_check("1\n5\n3\n"),
+            Ok(vec![Mismatch {
+                part: 2,
+                variant: "part2_off_by_one",
+                expected: Ok("5".to_string()),
+                answer: Ok("6".to_string()),
+            }])
+        );
+    }
+}
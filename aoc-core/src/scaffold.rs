@@ -0,0 +1,220 @@
+//! The `new-day` command, which lays out everything a new day needs:
+//! its module, registered with the year's runner, an empty example
+//! with its answers sidecar, and an empty input file to paste the
+//! puzzle input into.
+use crate::store;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    /// The day already has a module.
+    Exists(PathBuf),
+    /// `main.rs` doesn't have a `days![...]` list to add the day to.
+    NoRegistry(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            Error::Exists(path) => write!(f, "{} already exists", path.display()),
+            Error::NoRegistry(path) => write!(f, "no days![...] list in {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const TEMPLATE: &str = r#"use aoc_core::parse::{tag, ParseError};
+use nom::{character::complete::not_line_ending, multi::many0, sequence::terminated};
+
+aoc_core::solution! {
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<&str>, ParseError> {
+    aoc_core::parse::all(input, many0(terminated(not_line_ending, tag("\n"))))
+}
+
+pub fn part1(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+pub fn part2(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/{day}/example.txt");
+
+    #[test]
+    fn parses_example() {
+        let lines = parse_input(EXAMPLE).unwrap();
+        assert_eq!(lines.len(), EXAMPLE.lines().count());
+    }
+}
+"#;
+
+/// Scaffold `day` in the year crate at `root`, with its input in
+/// `inputs`. Files that are already there are left alone, except for
+/// the module itself which must be new. Returns the files written.
+pub fn new_day(root: &Path, inputs: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    let name = format!("day{:02}", day);
+    let module = root.join("src").join(format!("{}.rs", name));
+    if module.exists() {
+        return Err(Error::Exists(module));
+    }
+
+    let main = root.join("src").join("main.rs");
+    let registry = fs::read_to_string(&main).map_err(|err| Error::Io(main.clone(), err))?;
+    let registry = register(&registry, &name).ok_or_else(|| Error::NoRegistry(main.clone()))?;
+
+    let examples = root.join("examples").join(&name);
+    fs::create_dir_all(&examples).map_err(|err| Error::Io(examples.clone(), err))?;
+    fs::create_dir_all(inputs).map_err(|err| Error::Io(inputs.to_path_buf(), err))?;
+
+    let mut written = Vec::new();
+    for (path, content) in [
+        (module, TEMPLATE.replace("{day}", &name)),
+        (examples.join("example.txt"), String::new()),
+        (examples.join("example.toml"), String::new()),
+        (inputs.join(store::file_name(day)), String::new()),
+    ] {
+        if !path.exists() {
+            fs::write(&path, content).map_err(|err| Error::Io(path.clone(), err))?;
+            written.push(path);
+        }
+    }
+
+    fs::write(&main, registry).map_err(|err| Error::Io(main.clone(), err))?;
+    written.push(main);
+    Ok(written)
+}
+
+/// Add the `name` module to a year's `main.rs`, both as a `mod` and in
+/// its `days![...]` list, keeping them sorted. `None` if there's no list.
+fn register(main: &str, name: &str) -> Option<String> {
+    let start = main.find("days![")?;
+    let end = start + main[start..].find(']')?;
+
+    let mut days: Vec<&str> = main[start + "days![".len()..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|day| !day.is_empty())
+        .collect();
+    if !days.contains(&name) {
+        days.push(name);
+        days.sort_unstable();
+    }
+
+    let line_start = main[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let indent = &main[line_start..start];
+    let indent = &indent[..indent.len() - indent.trim_start().len()];
+
+    let mut registry = String::from(&main[..start]);
+    registry += &list(&main[line_start..start], indent, &days);
+    let rest = &main[end..];
+
+    // The `mod` lines go before the registry, after the last day that
+    // sorts before this one.
+    let declaration = format!("mod {};\n", name);
+    if registry.contains(&declaration) {
+        return Some(registry + rest);
+    }
+    let lines: Vec<&str> = registry.split_inclusive('\n').collect();
+    let at = lines
+        .iter()
+        .rposition(|line| line.starts_with("mod day") && *line < declaration.as_str())
+        .map(|idx| idx + 1)
+        .or_else(|| lines.iter().position(|line| line.starts_with("mod day")))
+        .or_else(|| lines.iter().position(|line| line.starts_with("fn ")))
+        .unwrap_or(lines.len());
+
+    let mut out = lines[..at].concat();
+    out += &declaration;
+    if !lines
+        .get(at)
+        .is_some_and(|line| line.starts_with("mod ") || line.trim().is_empty())
+    {
+        out.push('\n');
+    }
+    out += &lines[at..].concat();
+    Some(out + rest)
+}
+
+/// The `days![` list contents up to the closing bracket, laid out the
+/// way rustfmt would: on one line if it fits, else filling lines of up
+/// to 100 columns.
+fn list(before: &str, indent: &str, days: &[&str]) -> String {
+    let line = format!("days![{}", days.join(", "));
+    if before.len() + line.len() + "],".len() <= 100 {
+        return line;
+    }
+
+    let mut out = String::from("days![");
+    let mut row = String::new();
+    for day in days {
+        if !row.is_empty() && indent.len() + 4 + row.len() + day.len() + ",".len() > 100 {
+            out += &format!("\n{}    {}", indent, row.trim_end());
+            row.clear();
+        }
+        row += &format!("{}, ", day);
+    }
+    out += &format!("\n{}    {}\n{}", indent, row.trim_end(), indent);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = "\
+use aoc_core::{days, Year};
+
+mod day01;
+mod day03;
+
+fn year() -> Year {
+    Year {
+        year: 2024,
+        days: days![day01, day03,],
+    }
+}
+";
+
+    #[test]
+    fn registers_in_order() {
+        let main = register(MAIN, "day02").unwrap();
+        assert!(main.contains("mod day01;\nmod day02;\nmod day03;\n\nfn year()"));
+        assert!(main.contains("        days: days![day01, day02, day03],\n"));
+
+        let main = register(&main, "day25").unwrap();
+        assert!(main.contains("mod day03;\nmod day25;\n\nfn year()"));
+        assert_eq!(register(&main, "day25").unwrap(), main);
+    }
+
+    #[test]
+    fn wraps_long_registries() {
+        let mut main = MAIN.to_string();
+        for day in 2..=25 {
+            main = register(&main, &format!("day{:02}", day)).unwrap();
+        }
+        assert!(main.contains(
+            "        days: days![\n            \
+                 day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,\n            \
+                 day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24,\n            \
+                 day25,\n        \
+             ],\n"
+        ));
+        assert!(register("fn main() {}\n", "day01").is_none());
+    }
+}
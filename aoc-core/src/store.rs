@@ -0,0 +1,230 @@
+//! Where the puzzle inputs live, and how the missing ones get there.
+//!
+//! Every year keeps its inputs in its crate's `data` directory, as
+//! `dayNN.txt`, next to the `answers.toml`, `recorded.toml` and
+//! `baseline.toml` that go with them. An input that isn't there yet is
+//! fetched with the store's [`Fetch`] backend, if it has one, and saved
+//! in the store so it's never requested twice.
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Where the years' crates live, next to this one.
+pub fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// The directory holding `year`'s inputs.
+pub fn dir(year: u16) -> PathBuf {
+    workspace().join(format!("aoc-{}", year)).join("data")
+}
+
+/// The name `day`'s input goes by in the store.
+pub fn file_name(day: u8) -> String {
+    format!("day{:02}.txt", day)
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    /// Neither in the store nor anywhere to fetch it from.
+    Missing(PathBuf),
+    Fetch(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(path, err) => write!(f, "can't read {}: {}", path.display(), err),
+            Error::Missing(path) => write!(f, "no input at {}", path.display()),
+            Error::Fetch(err) => write!(f, "can't fetch input: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Somewhere to get inputs from.
+pub trait Fetch {
+    /// The input of the given puzzle, or `None` if there's none to get.
+    fn fetch(&self, year: u16, day: u8) -> Result<Option<String>, String>;
+}
+
+/// A year's inputs.
+pub struct Store {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetch>>,
+}
+
+impl Store {
+    /// The inputs in `dir`, without fetching the missing ones.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            fetcher: None,
+        }
+    }
+
+    /// Fetch missing inputs with `fetcher`.
+    pub fn with_fetcher(mut self, fetcher: impl Fetch + 'static) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(file_name(day))
+    }
+
+    /// The input of `day`, fetching it into the store first if needed.
+    /// Empty inputs, such as the ones `new-day` leaves, count as missing.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        let path = self.path(day);
+        match fs::read_to_string(&path) {
+            Ok(input) if !input.is_empty() => return Ok(input),
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(Error::Io(path, err)),
+        }
+
+        let Some(fetcher) = &self.fetcher else {
+            return Err(Error::Missing(path));
+        };
+        let Some(input) = fetcher.fetch(year, day).map_err(Error::Fetch)? else {
+            return Err(Error::Missing(path));
+        };
+        fs::create_dir_all(&self.dir).map_err(|err| Error::Io(self.dir.clone(), err))?;
+        fs::write(&path, &input).map_err(|err| Error::Io(path, err))?;
+        Ok(input)
+    }
+}
+
+/// Copies inputs from another directory, for working offline off a
+/// backup or someone else's checkout. Besides the store's own layout,
+/// it finds inputs with unpadded names (`day1.txt`) and in per-year
+/// subdirectories (`2020/day1.txt`).
+pub struct Dir(pub PathBuf);
+
+impl Fetch for Dir {
+    fn fetch(&self, year: u16, day: u8) -> Result<Option<String>, String> {
+        let names = [file_name(day), format!("day{}.txt", day)];
+        let dirs = [self.0.clone(), self.0.join(year.to_string())];
+        for path in dirs
+            .iter()
+            .flat_map(|dir| names.iter().map(|name| dir.join(name)))
+        {
+            match fs::read_to_string(&path) {
+                Ok(input) => return Ok(Some(input)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(format!("{}: {}", path.display(), err)),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Spaces out another fetcher's requests by at least `interval`.
+pub struct RateLimited<F> {
+    fetcher: F,
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl<F: Fetch> RateLimited<F> {
+    pub fn new(fetcher: F, interval: Duration) -> Self {
+        Self {
+            fetcher,
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl<F: Fetch> Fetch for RateLimited<F> {
+    fn fetch(&self, year: u16, day: u8) -> Result<Option<String>, String> {
+        let mut last = self.last.lock().unwrap();
+        if let Some(wait) = last.map(|last| self.interval.saturating_sub(last.elapsed())) {
+            thread::sleep(wait);
+        }
+        let input = self.fetcher.fetch(year, day);
+        *last = Some(Instant::now());
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, env, process, rc::Rc};
+
+    /// Serves made up inputs, counting the requests.
+    struct Mock(Rc<Cell<usize>>);
+
+    impl Fetch for Mock {
+        fn fetch(&self, year: u16, day: u8) -> Result<Option<String>, String> {
+            self.0.set(self.0.get() + 1);
+            Ok((day < 25).then(|| format!("{} {}\n", year, day)))
+        }
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-store-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_inputs_once() {
+        let dir = scratch("once");
+        let requests = Rc::new(Cell::new(0));
+        let store = Store::new(&dir).with_fetcher(Mock(requests.clone()));
+
+        assert_eq!(store.input(2021, 3).unwrap(), "2021 3\n");
+        assert_eq!(store.input(2021, 3).unwrap(), "2021 3\n");
+        assert_eq!(requests.get(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.join("day03.txt")).unwrap(),
+            "2021 3\n"
+        );
+
+        assert!(matches!(store.input(2021, 25), Err(Error::Missing(_))));
+        assert!(matches!(
+            Store::new(&dir).input(2021, 4),
+            Err(Error::Missing(_))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn copies_from_other_layouts() {
+        let dir = scratch("layouts");
+        fs::create_dir_all(dir.join("2020")).unwrap();
+        fs::write(dir.join("2020").join("day1.txt"), "1\n").unwrap();
+        fs::write(dir.join("day02.txt"), "2\n").unwrap();
+
+        let backup = Dir(dir.clone());
+        assert_eq!(backup.fetch(2020, 1), Ok(Some("1\n".to_string())));
+        assert_eq!(backup.fetch(2020, 2), Ok(Some("2\n".to_string())));
+        assert_eq!(backup.fetch(2020, 3), Ok(None));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let interval = Duration::from_millis(50);
+        let fetcher = RateLimited::new(Mock(Rc::default()), interval);
+
+        let start = Instant::now();
+        for day in 1..=3 {
+            fetcher.fetch(2021, day).unwrap();
+        }
+        assert!(start.elapsed() >= 2 * interval);
+    }
+}
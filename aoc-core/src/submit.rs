@@ -0,0 +1,478 @@
+//! Submitting answers, keeping track of every attempt so that a known
+//! wrong answer is never submitted twice.
+//!
+//! The attempts are appended to `attempts.log` next to the inputs, one
+//! per line, with tab-separated fields: when it was submitted, as a Unix
+//! timestamp, the day, the part, the outcome and the answer. Outcomes
+//! are `correct`, `too-high`, `too-low`, `wrong`, `wrong-level` for a
+//! part already solved or still locked, and `wait-N` for an answer
+//! given too soon, N seconds before another can be.
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Somewhere to submit answers to.
+pub trait Submit {
+    /// Submit `answer` to the given part, returning the page sent back.
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String, String>;
+}
+
+/// What became of a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Answered too recently, with the time left to wait.
+    Wait(Duration),
+    /// The part isn't the one to solve next: already solved, or locked.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Make sense of the page sent back for a submission.
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            Some(if page.contains("answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            // "You have 1m 5s left to wait."
+            let left = page
+                .split("You have ")
+                .nth(1)?
+                .split(" left to wait")
+                .next()?;
+            let mut wait = 0;
+            for amount in left.split_whitespace() {
+                let (count, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let unit = match unit {
+                    "h" => 3600,
+                    "m" => 60,
+                    "s" => 1,
+                    _ => return None,
+                };
+                wait += count.parse::<u64>().ok()? * unit;
+            }
+            Some(Outcome::Wait(Duration::from_secs(wait)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Outcome::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the answer was judged wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(wait) => write!(f, "wait-{}", wait.as_secs()),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = ();
+
+    fn from_str(outcome: &str) -> Result<Self, Self::Err> {
+        match outcome {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => {
+                let wait = outcome.strip_prefix("wait-").ok_or(())?;
+                Ok(Outcome::Wait(Duration::from_secs(
+                    wait.parse().map_err(|_| ())?,
+                )))
+            }
+        }
+    }
+}
+
+/// A past submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// When it was submitted, in seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.time, self.day, self.part, self.outcome, self.answer
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = ();
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.splitn(5, '\t');
+        let mut field = || fields.next().ok_or(());
+        Ok(Attempt {
+            time: field()?.parse().map_err(|_| ())?,
+            day: field()?.parse().map_err(|_| ())?,
+            part: field()?.parse().map_err(|_| ())?,
+            outcome: field()?.parse()?,
+            answer: field()?.to_string(),
+        })
+    }
+}
+
+/// Why an answer wasn't submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved, with the given answer.
+    Solved(String),
+    /// The very same answer was already judged wrong.
+    Tried(Outcome),
+    /// Another answer was already judged too high or too low, and this
+    /// one's past it.
+    Beyond(String, Outcome),
+    /// The last attempt asked to wait, for that much longer.
+    Wait(Duration),
+    /// The answers file expects another answer.
+    Expected(String),
+    /// The answer can't be sent as it is.
+    Unsendable,
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "the part was already solved with {}", answer),
+            Refusal::Tried(outcome) => write!(f, "it was already submitted: {}", outcome),
+            Refusal::Beyond(answer, outcome) => {
+                write!(f, "{} was already submitted: {}", answer, outcome)
+            }
+            Refusal::Wait(wait) => write!(f, "wait {}s before trying again", wait.as_secs()),
+            Refusal::Expected(answer) => write!(f, "the answers file expects {}", answer),
+            Refusal::Unsendable => write!(f, "answers can't be empty or span lines"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    /// A line of the attempts log that doesn't make sense.
+    Log(PathBuf, usize),
+    Refused(Refusal),
+    Client(String),
+    /// A response that doesn't say what became of the answer.
+    Response(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            Error::Log(path, line) => write!(f, "{}: bad attempt on line {}", path.display(), line),
+            Error::Refused(refusal) => write!(f, "not submitting, {}", refusal),
+            Error::Client(err) => write!(f, "failed to submit: {}", err),
+            Error::Response(page) => write!(f, "unexpected response:\n{}", page),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Every attempt made at a year's puzzles.
+#[derive(Debug, Default)]
+pub struct History(Vec<Attempt>);
+
+impl History {
+    /// Load the attempts log. A missing log means nothing was tried yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(Error::Io(path.to_path_buf(), err)),
+        };
+
+        content
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.parse()
+                    .map_err(|_| Error::Log(path.to_path_buf(), idx + 1))
+            })
+            .collect::<Result<_, _>>()
+            .map(History)
+    }
+
+    /// Add an attempt to the log at `path`, and to the history.
+    pub fn append(&mut self, path: &Path, attempt: Attempt) -> Result<(), Error> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut log| writeln!(log, "{}", attempt))
+            .map_err(|err| Error::Io(path.to_path_buf(), err))?;
+        self.0.push(attempt);
+        Ok(())
+    }
+
+    pub fn attempts(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.0
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    /// Whether `answer` is worth submitting at `now`, going by the
+    /// earlier attempts.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if answer.is_empty() || answer.contains(['\n', '\t']) {
+            return Err(Refusal::Unsendable);
+        }
+
+        let value = answer.parse::<i128>().ok();
+        for attempt in self.attempts(day, part) {
+            match attempt.outcome {
+                Outcome::Correct => return Err(Refusal::Solved(attempt.answer.clone())),
+                outcome if outcome.is_wrong() && attempt.answer == answer => {
+                    return Err(Refusal::Tried(outcome))
+                }
+                outcome @ (Outcome::TooHigh | Outcome::TooLow) => {
+                    let Some((value, bound)) = value.zip(attempt.answer.parse::<i128>().ok())
+                    else {
+                        continue;
+                    };
+                    if (outcome == Outcome::TooHigh && value >= bound)
+                        || (outcome == Outcome::TooLow && value <= bound)
+                    {
+                        return Err(Refusal::Beyond(attempt.answer.clone(), outcome));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // The site makes you wait after every wrong answer, and says how
+        // long when you try too soon.
+        if let Some(Attempt {
+            time,
+            outcome: Outcome::Wait(wait),
+            ..
+        }) = self.attempts(day, part).last()
+        {
+            let left = (time + wait.as_secs()).saturating_sub(now);
+            if left > 0 {
+                return Err(Refusal::Wait(Duration::from_secs(left)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The current time, as a Unix timestamp.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Submit `answer` with `client`, unless the attempts logged at `log`
+/// already rule it out, and log the attempt.
+pub fn submit(
+    client: &dyn Submit,
+    log: &Path,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome, Error> {
+    let mut history = History::load(log)?;
+    let time = now();
+    history
+        .check(day, part, answer, time)
+        .map_err(Error::Refused)?;
+
+    let page = client
+        .submit(year, day, part, answer)
+        .map_err(Error::Client)?;
+    let outcome = Outcome::parse(&page).ok_or_else(|| Error::Response(article(&page)))?;
+    history.append(
+        log,
+        Attempt {
+            time,
+            day,
+            part,
+            outcome,
+            answer: answer.to_string(),
+        },
+    )?;
+    Ok(outcome)
+}
+
+/// The gist of a page, for showing a response that wasn't understood.
+fn article(page: &str) -> String {
+    let start = page.find("<article>").unwrap_or(0);
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+    page[start..end].chars().take(500).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, env, process};
+
+    const WRONG: &str = "<article><p>That's not the right answer; your answer is too high.  \
+                         If you're stuck, make sure you're using the full input data; please \
+                         wait one minute before trying again.</p></article>";
+
+    #[test]
+    fn can_parse_outcomes() {
+        assert_eq!(
+            Outcome::parse("<p>That's the right answer!  You are <em>one gold star</em> closer"),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(Outcome::parse(WRONG), Some(Outcome::TooHigh));
+        assert_eq!(
+            Outcome::parse("<p>That's not the right answer.  If you're stuck"),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<p>You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 1m 5s left to wait."
+            ),
+            Some(Outcome::Wait(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Outcome::parse("<p>You don't seem to be solving the right level.  Did you already"),
+            Some(Outcome::WrongLevel)
+        );
+        assert_eq!(Outcome::parse("<p>Something else</p>"), None);
+    }
+
+    #[test]
+    fn round_trips_attempts() {
+        let attempt = Attempt {
+            time: 1701407012,
+            day: 1,
+            part: 2,
+            outcome: Outcome::Wait(Duration::from_secs(23)),
+            answer: "a,b c".to_string(),
+        };
+        assert_eq!(attempt.to_string().parse(), Ok(attempt));
+        assert_eq!("1\t1\t2\tmaybe\t3".parse::<Attempt>(), Err(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let attempt = |time, part, outcome, answer: &str| Attempt {
+            time,
+            day: 1,
+            part,
+            outcome,
+            answer: answer.to_string(),
+        };
+        let history = History(vec![
+            attempt(100, 1, Outcome::TooHigh, "500"),
+            attempt(160, 1, Outcome::TooLow, "20"),
+            attempt(220, 1, Outcome::Wrong, "abc"),
+            attempt(230, 1, Outcome::Wait(Duration::from_secs(30)), "30"),
+            attempt(100, 2, Outcome::Correct, "7"),
+        ]);
+
+        assert_eq!(
+            history.check(1, 1, "500", 300),
+            Err(Refusal::Tried(Outcome::TooHigh))
+        );
+        assert_eq!(
+            history.check(1, 1, "600", 300),
+            Err(Refusal::Beyond("500".to_string(), Outcome::TooHigh))
+        );
+        assert_eq!(
+            history.check(1, 1, "12", 300),
+            Err(Refusal::Beyond("20".to_string(), Outcome::TooLow))
+        );
+        assert_eq!(
+            history.check(1, 1, "abc", 300),
+            Err(Refusal::Tried(Outcome::Wrong))
+        );
+        assert_eq!(
+            history.check(1, 1, "30", 250),
+            Err(Refusal::Wait(Duration::from_secs(10)))
+        );
+        assert_eq!(history.check(1, 1, "30", 300), Ok(()));
+        assert_eq!(
+            history.check(1, 2, "8", 300),
+            Err(Refusal::Solved("7".to_string()))
+        );
+        assert_eq!(history.check(1, 1, "", 300), Err(Refusal::Unsendable));
+    }
+
+    /// Answers with canned pages, keeping the answers it was sent.
+    struct Mock(RefCell<Vec<String>>);
+
+    impl Submit for Mock {
+        fn submit(&self, _: u16, _: u8, _: u8, answer: &str) -> Result<String, String> {
+            self.0.borrow_mut().push(answer.to_string());
+            Ok(match answer {
+                "42" => "<p>That's the right answer!".to_string(),
+                _ => WRONG.to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn logs_every_attempt() {
+        let log = env::temp_dir().join(format!("aoc-attempts-{}.log", process::id()));
+        let _ = fs::remove_file(&log);
+        let client = Mock(RefCell::default());
+
+        assert_eq!(
+            submit(&client, &log, 2021, 1, 1, "50").unwrap(),
+            Outcome::TooHigh
+        );
+        assert!(matches!(
+            submit(&client, &log, 2021, 1, 1, "51"),
+            Err(Error::Refused(Refusal::Beyond(_, Outcome::TooHigh)))
+        ));
+        assert_eq!(
+            submit(&client, &log, 2021, 1, 1, "42").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(*client.0.borrow(), ["50", "42"]);
+
+        let history = History::load(&log).unwrap();
+        let outcomes: Vec<Outcome> = history.attempts(1, 1).map(|a| a.outcome).collect();
+        assert_eq!(outcomes, [Outcome::TooHigh, Outcome::Correct]);
+        fs::remove_file(log).unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+//! The small TOML subset that the answers and baseline files share: a
+//! `[dayNN]` table for each day, holding one `key = value` entry per
+//! line, with blank lines and `#` comments in between. Each file brings
+//! its own keys and values, and this module holds everything else, so
+//! the two formats can't drift apart.
+use crate::parse::{tag, IResult};
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1, not_line_ending, space0},
+    combinator::{map, map_res, opt, value},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+};
+
+/// A line of a file, with entries of type `T`.
+pub(crate) enum Line<T> {
+    Table(u8),
+    Entry(T),
+    Blank,
+}
+
+fn comment(input: &str) -> IResult<&str, ()> {
+    value((), pair(char('#'), not_line_ending))(input)
+}
+
+fn table(input: &str) -> IResult<&str, u8> {
+    delimited(
+        char('['),
+        preceded(tag("day"), map_res(digit1, str::parse)),
+        char(']'),
+    )(input)
+}
+
+/// A `partN` key.
+pub(crate) fn part(input: &str) -> IResult<&str, u8> {
+    preceded(tag("part"), map_res(digit1, str::parse))(input)
+}
+
+/// A `key = value` entry.
+pub(crate) fn entry<'a, K, V>(
+    key: impl FnMut(&'a str) -> IResult<&'a str, K>,
+    value: impl FnMut(&'a str) -> IResult<&'a str, V>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, tuple((space0, char('='), space0)), value)
+}
+
+/// A whole line, newline included, with its entries parsed by `entry`.
+pub(crate) fn line<'a, T>(
+    entry: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Line<T>> {
+    terminated(
+        delimited(
+            space0,
+            alt((
+                map(table, Line::Table),
+                map(entry, Line::Entry),
+                map(space0, |_| Line::Blank),
+            )),
+            pair(space0, opt(comment)),
+        ),
+        char('\n'),
+    )
+}